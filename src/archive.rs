@@ -0,0 +1,288 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use radius_sdk::{kvstore::KvStoreError, signature::Address};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::types::*;
+
+/// Bumped whenever the layout of [`ArchiveRecord`] changes in a way older
+/// nodes cannot read.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A key archive is a JSON Lines file. The first line is a [`ArchiveHeader`],
+/// followed by any number of [`ArchiveRecord`]s and terminated by a single
+/// [`ArchiveFooter`] carrying the SHA-256 checksum over every preceding line
+/// (including its trailing newline). Nothing may follow the footer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchiveHeader {
+    pub version: u32,
+    pub from_key_id: KeyId,
+    pub to_key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArchiveRecord {
+    KeyId(KeyId),
+    KeyGeneratorList(KeyGeneratorList),
+    Round(RoundRecord),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoundRecord {
+    pub key_id: KeyId,
+    pub partial_keys: Vec<(Address, PartialKey)>,
+    pub aggregated_key: Option<AggregatedKey>,
+    pub decryption_key: Option<DecryptionKey>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchiveFooter {
+    pub record_count: u64,
+    pub checksum: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveSummary {
+    pub round_count: u64,
+    pub aggregated_key_count: u64,
+    pub decryption_key_count: u64,
+}
+
+/// Writes every round between `from_key_id` and `to_key_id` (both inclusive)
+/// along with the current [`KeyId`] and [`KeyGeneratorList`] to `path`.
+///
+/// The database must be initialized before calling this function.
+pub fn export_archive(
+    path: impl AsRef<Path>,
+    from_key_id: KeyId,
+    to_key_id: KeyId,
+) -> Result<ArchiveSummary, ArchiveError> {
+    if from_key_id.as_u64() > to_key_id.as_u64() {
+        return Err(ArchiveError::InvalidRange);
+    }
+
+    let file = File::create(path).map_err(ArchiveError::Io)?;
+    let mut writer = ChecksumWriter::new(BufWriter::new(file));
+    let mut summary = ArchiveSummary::default();
+
+    writer.write_line(&ArchiveHeader {
+        version: ARCHIVE_VERSION,
+        from_key_id,
+        to_key_id,
+    })?;
+
+    writer.write_line(&ArchiveRecord::KeyId(KeyId::get()?))?;
+    writer.write_line(&ArchiveRecord::KeyGeneratorList(KeyGeneratorList::get()?))?;
+
+    for key_id in from_key_id.as_u64()..=to_key_id.as_u64() {
+        let key_id = KeyId::new(key_id);
+
+        let partial_keys = match PartialKeyAddressList::get(key_id) {
+            Ok(partial_key_address_list) => partial_key_address_list
+                .to_vec()
+                .into_iter()
+                .map(|address| {
+                    let partial_key = PartialKey::get(key_id, &address)?;
+                    Ok((address, partial_key))
+                })
+                .collect::<Result<Vec<_>, KvStoreError>>()?,
+            Err(_) => Vec::new(),
+        };
+        let aggregated_key = AggregatedKey::get(key_id).ok();
+        let decryption_key = DecryptionKey::get(key_id).ok();
+
+        if partial_keys.is_empty() && aggregated_key.is_none() && decryption_key.is_none() {
+            continue;
+        }
+
+        summary.round_count += 1;
+        summary.aggregated_key_count += aggregated_key.is_some() as u64;
+        summary.decryption_key_count += decryption_key.is_some() as u64;
+
+        writer.write_line(&ArchiveRecord::Round(RoundRecord {
+            key_id,
+            partial_keys,
+            aggregated_key,
+            decryption_key,
+        }))?;
+    }
+
+    writer.finish()?;
+
+    Ok(summary)
+}
+
+/// Verifies the archive at `path` and writes its contents into the database.
+///
+/// The archive is read and checked in full before anything is written, so a
+/// corrupted file leaves the database untouched. The stored [`KeyId`] is only
+/// ever moved forward.
+pub fn import_archive(path: impl AsRef<Path>) -> Result<ArchiveSummary, ArchiveError> {
+    let file = File::open(path).map_err(ArchiveError::Io)?;
+    let mut lines = BufReader::new(file).lines();
+    let mut hasher = Sha256::new();
+
+    let mut next_line = |hasher: &mut Sha256| -> Result<Option<String>, ArchiveError> {
+        match lines.next() {
+            Some(line) => {
+                let line = line.map_err(ArchiveError::Io)?;
+                hasher.update(line.as_bytes());
+                hasher.update(b"\n");
+                Ok(Some(line))
+            }
+            None => Ok(None),
+        }
+    };
+
+    let header_line = next_line(&mut hasher)?.ok_or(ArchiveError::MissingHeader)?;
+    let header: ArchiveHeader = serde_json::from_str(&header_line).map_err(ArchiveError::Parse)?;
+    if header.version != ARCHIVE_VERSION {
+        return Err(ArchiveError::UnsupportedVersion(header.version));
+    }
+
+    let mut records = Vec::new();
+    let footer = loop {
+        let checksum = const_hex::encode(hasher.clone().finalize());
+        let line = next_line(&mut hasher)?.ok_or(ArchiveError::MissingFooter)?;
+
+        if let Ok(record) = serde_json::from_str::<ArchiveRecord>(&line) {
+            records.push(record);
+            continue;
+        }
+
+        let footer: ArchiveFooter = serde_json::from_str(&line).map_err(ArchiveError::Parse)?;
+        if footer.checksum != checksum {
+            return Err(ArchiveError::ChecksumMismatch);
+        }
+        break footer;
+    };
+
+    // The checksum does not cover anything after the footer.
+    if next_line(&mut hasher)?.is_some() {
+        return Err(ArchiveError::TrailingData);
+    }
+
+    if footer.record_count != records.len() as u64 {
+        return Err(ArchiveError::RecordCountMismatch);
+    }
+
+    let mut summary = ArchiveSummary::default();
+    for record in records {
+        match record {
+            ArchiveRecord::KeyId(archived_key_id) => {
+                let mut key_id = KeyId::get_mut()?;
+                if archived_key_id.as_u64() > key_id.as_u64() {
+                    *key_id = archived_key_id;
+                }
+                key_id.update()?;
+            }
+            ArchiveRecord::KeyGeneratorList(archived_key_generator_list) => {
+                KeyGeneratorList::apply(|key_generator_list| {
+                    for key_generator in archived_key_generator_list.iter() {
+                        key_generator_list.insert(key_generator.clone());
+                    }
+                })?;
+            }
+            ArchiveRecord::Round(round) => {
+                summary.round_count += 1;
+
+                let mut partial_key_address_list =
+                    PartialKeyAddressList::get_or(round.key_id, PartialKeyAddressList::default)?;
+                for (address, partial_key) in round.partial_keys {
+                    partial_key.put(round.key_id, &address)?;
+                    partial_key_address_list.insert(address);
+                }
+                partial_key_address_list.put(round.key_id)?;
+
+                if let Some(aggregated_key) = round.aggregated_key {
+                    aggregated_key.put(round.key_id)?;
+                    LatestAggregatedKeyId::advance(round.key_id)?;
+                    summary.aggregated_key_count += 1;
+                }
+
+                if let Some(decryption_key) = round.decryption_key {
+                    decryption_key.put(round.key_id)?;
+                    LatestDecryptionKeyId::advance(round.key_id)?;
+                    summary.decryption_key_count += 1;
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+struct ChecksumWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    record_count: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            record_count: 0,
+        }
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<(), ArchiveError> {
+        let mut line = serde_json::to_vec(value).map_err(ArchiveError::Parse)?;
+        line.push(b'\n');
+
+        self.hasher.update(&line);
+        self.inner.write_all(&line).map_err(ArchiveError::Io)?;
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), ArchiveError> {
+        let footer = ArchiveFooter {
+            // The header is not a record.
+            record_count: self.record_count - 1,
+            checksum: const_hex::encode(self.hasher.finalize()),
+        };
+
+        let mut line = serde_json::to_vec(&footer).map_err(ArchiveError::Parse)?;
+        line.push(b'\n');
+        self.inner.write_all(&line).map_err(ArchiveError::Io)?;
+        self.inner.flush().map_err(ArchiveError::Io)
+    }
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Database(KvStoreError),
+
+    InvalidRange,
+    MissingHeader,
+    MissingFooter,
+    TrailingData,
+    UnsupportedVersion(u32),
+    ChecksumMismatch,
+    RecordCountMismatch,
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+impl From<KvStoreError> for ArchiveError {
+    fn from(value: KvStoreError) -> Self {
+        Self::Database(value)
+    }
+}
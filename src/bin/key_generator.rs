@@ -1,23 +1,29 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use distributed_key_generation::{
+    archive::{export_archive, import_archive},
+    client::{ClientOptions, ClusterClient, ExternalClient},
+    database,
     error::{self, Error},
+    health::run_health_server,
+    logger::{init_default_logger, init_logger},
+    publisher::key_publisher,
     rpc::{
         cluster::{self, GetKeyGeneratorList, GetKeyGeneratorRpcUrlListResponse},
-        external, internal,
+        external, internal, run_rpc_gateway,
+        transport::{self, ClusterTransport, JsonRpcTransport, TlsTransport},
+        GatewayTls, Instrumented,
     },
     state::AppState,
-    task::single_key_generator::run_single_key_generator,
-    types::*,
-};
-use radius_sdk::{
-    json_rpc::{
-        client::{Id, RpcClient},
-        server::{RpcParameter, RpcServer},
+    task::{
+        heartbeat::run_heartbeat, publisher::run_key_publisher, registry::run_registry_sync,
+        single_key_generator::run_single_key_generator, webhook::run_webhook_dispatcher,
     },
-    kvstore::KvStoreBuilder,
+    tls::{mutual_tls_acceptor, tls_acceptor},
+    types::*,
 };
+use radius_sdk::json_rpc::server::RpcServer;
 pub use serde::{Deserialize, Serialize};
 use skde::{setup, BigUint};
 use tokio::task::JoinHandle;
@@ -49,14 +55,125 @@ pub enum Commands {
         #[clap(flatten)]
         config_option: Box<ConfigOption>,
     },
+
+    /// Exports the key history of a stopped node to an archive file
+    Export {
+        #[clap(flatten)]
+        config_option: Box<ConfigOption>,
+
+        #[doc = "Set the first key id to export"]
+        #[clap(long = "from")]
+        from: u64,
+
+        #[doc = "Set the last key id to export (inclusive)"]
+        #[clap(long = "to")]
+        to: u64,
+
+        #[doc = "Set the archive file path to write to"]
+        #[clap(long = "output")]
+        output: PathBuf,
+    },
+
+    /// Imports an archive file into the database of a stopped node
+    Import {
+        #[clap(flatten)]
+        config_option: Box<ConfigOption>,
+
+        #[doc = "Set the archive file path to read from"]
+        #[clap(long = "input")]
+        input: PathBuf,
+    },
+
+    /// Prints the status of a running node
+    Status {
+        #[clap(flatten)]
+        query_option: QueryOption,
+    },
+
+    /// Prints the latest encryption key of a running node
+    LatestKey {
+        #[clap(flatten)]
+        query_option: QueryOption,
+    },
+
+    /// Prints the encryption key for a key id
+    EncryptionKey {
+        #[clap(flatten)]
+        query_option: QueryOption,
+
+        #[doc = "Set the key id to query"]
+        #[clap(long = "id")]
+        id: u64,
+    },
+
+    /// Prints the decryption key for a key id
+    DecryptionKey {
+        #[clap(flatten)]
+        query_option: QueryOption,
+
+        #[doc = "Set the key id to query"]
+        #[clap(long = "id")]
+        id: u64,
+    },
+
+    /// Prints the key generators known to a running node
+    Members {
+        #[clap(flatten)]
+        query_option: QueryOption,
+    },
+
+    /// Prints the SKDE parameters of a running node
+    Params {
+        #[clap(flatten)]
+        query_option: QueryOption,
+    },
+}
+
+impl Commands {
+    fn loads_config(&self) -> bool {
+        matches!(
+            self,
+            Self::Start { .. } | Self::Export { .. } | Self::Import { .. }
+        )
+    }
+}
+
+#[derive(Args, Debug, Deserialize, Serialize)]
+pub struct QueryOption {
+    #[doc = "Set the rpc url of the node to query (the cluster rpc url for `members`)"]
+    #[clap(long = "rpc-url")]
+    pub rpc_url: String,
+
+    #[doc = "Print the raw JSON response"]
+    #[clap(long = "json")]
+    pub json: bool,
+
+    #[doc = "Set the api key sent to the external rpc server"]
+    #[clap(long = "api-key")]
+    pub api_key: Option<String>,
+
+    #[doc = "Set a PEM CA bundle to trust (the cluster CA for `members` over mutual TLS)"]
+    #[clap(long = "ca-path")]
+    pub ca_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM cluster certificate presented by `members` over mutual TLS"]
+    #[clap(long = "tls-cert-path")]
+    pub tls_cert_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM private key of the cluster certificate"]
+    #[clap(long = "tls-key-path")]
+    pub tls_key_path: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt().init();
-
     let mut cli = Cli::init();
 
+    // Commands that load a configuration set up the logger from it instead.
+    if !cli.command.loads_config() {
+        init_default_logger();
+    }
+
     match cli.command {
         Commands::Init { ref config_path } => ConfigPath::init(config_path)?,
         Commands::Start {
@@ -64,6 +181,7 @@ async fn main() -> Result<(), Error> {
         } => {
             // Load the configuration from the path
             let config = Config::load(config_option)?;
+            let _log_guard = init_logger(&config)?;
 
             tracing::info!(
                 "Successfully loaded the configuration file at {:?}.",
@@ -86,46 +204,51 @@ async fn main() -> Result<(), Error> {
             let skde_params = setup(time, p, q, g, max_key_generator_number);
 
             // Initialize the database
-            KvStoreBuilder::default()
-                .set_default_lock_timeout(5000)
-                .set_txn_lock_timeout(5000)
-                .build(config.database_path())
-                .map_err(error::Error::Database)?
-                .init();
+            initialize_database(&config)?;
 
-            KeyGeneratorList::initialize().map_err(error::Error::Database)?;
-            KeyId::initialize().map_err(error::Error::Database)?;
-
-            tracing::info!(
-                "Successfully initialized the database at {:?}.",
-                config.database_path(),
-            );
+            // Cluster RPCs go over mutual TLS when the cluster certificates are
+            // configured
+            let cluster_transport: Arc<dyn ClusterTransport> = match config.cluster_tls() {
+                Some(tls_files) => Arc::new(TlsTransport::new(tls_files)?),
+                None => Arc::new(JsonRpcTransport::new()?),
+            };
 
             if let Some(seed_rpc_url) = config.seed_cluster_rpc_url() {
                 // Follow
                 // Initialize the cluster RPC server
-                let rpc_client: RpcClient = RpcClient::new()?;
-
-                let response: GetKeyGeneratorRpcUrlListResponse = rpc_client
-                    .request(
-                        seed_rpc_url,
-                        GetKeyGeneratorList::method(),
-                        &GetKeyGeneratorList,
-                        Id::Null,
-                    )
-                    .await?;
+                let response: GetKeyGeneratorRpcUrlListResponse = transport::request(
+                    cluster_transport.as_ref(),
+                    seed_rpc_url,
+                    &GetKeyGeneratorList,
+                )
+                .await?;
 
-                let key_generator_list: KeyGeneratorList =
-                    response.key_generator_rpc_url_list.into();
+                let key_generator_list =
+                    KeyGeneratorList::try_from(response.key_generator_rpc_url_list)
+                        .map_err(|_| Error::HexDecodeError)?;
 
                 key_generator_list.put()?;
             }
 
             // Initialize an application-wide state instance
-            let app_state = AppState::new(config, skde_params);
+            let app_state = AppState::new(config, skde_params, cluster_transport)?;
+
+            // Deliver key events to the configured webhooks
+            run_webhook_dispatcher(app_state.clone());
+
+            // Track which peers are reachable
+            run_heartbeat(app_state.clone());
+
+            // Follow the key generator registry contract, if configured
+            run_registry_sync(app_state.clone());
 
             if app_state.config().seed_cluster_rpc_url().is_none() {
                 // Leader
+                // Publish the commitment of every aggregated key, if configured
+                if let Some(key_publisher) = key_publisher(app_state.config())? {
+                    run_key_publisher(key_publisher);
+                }
+
                 // Run the single key generator task
                 run_single_key_generator(app_state.clone());
             }
@@ -136,22 +259,254 @@ async fn main() -> Result<(), Error> {
             // Initialize the cluster RPC server
             initialize_cluster_rpc_server(&app_state).await?;
 
+            // Initialize the health check server
+            if let Some(health_check_address) = app_state.config().health_check_address()? {
+                run_health_server(app_state.clone(), health_check_address).await?;
+            }
+
             // Initialize the external RPC server.
             let server_handle = initialize_external_rpc_server(&app_state).await?;
 
             server_handle.await.unwrap();
         }
+        Commands::Export {
+            ref mut config_option,
+            from,
+            to,
+            ref output,
+        } => {
+            let config = Config::load(config_option)?;
+            let _log_guard = init_logger(&config)?;
+            initialize_database(&config)?;
+
+            let summary = export_archive(output, KeyId::new(from), KeyId::new(to))?;
+
+            tracing::info!(
+                "Exported {} rounds ({} encryption keys / {} decryption keys) to {:?}.",
+                summary.round_count,
+                summary.aggregated_key_count,
+                summary.decryption_key_count,
+                output,
+            );
+        }
+        Commands::Import {
+            ref mut config_option,
+            ref input,
+        } => {
+            let config = Config::load(config_option)?;
+            let _log_guard = init_logger(&config)?;
+            initialize_database(&config)?;
+
+            let summary = import_archive(input)?;
+
+            tracing::info!(
+                "Imported {} rounds ({} encryption keys / {} decryption keys) from {:?}.",
+                summary.round_count,
+                summary.aggregated_key_count,
+                summary.decryption_key_count,
+                input,
+            );
+        }
+        Commands::Status { ref query_option } => {
+            let response = external_client(query_option)?.get_node_status().await?;
+
+            print_response(query_option, &response, || {
+                let format_key_id = |key_id: Option<KeyId>| {
+                    key_id
+                        .map(|key_id| key_id.as_u64().to_string())
+                        .unwrap_or_else(|| "-".to_owned())
+                };
+
+                let mut lines = vec![
+                    format!("version: {}", response.version),
+                    format!("role: {:?}", response.role),
+                    format!("address: {}", response.address),
+                    format!("key id: {}", response.key_id.as_u64()),
+                    format!(
+                        "latest encryption key id: {}",
+                        format_key_id(response.latest_aggregated_key_id)
+                    ),
+                    format!(
+                        "latest decryption key id: {}",
+                        format_key_id(response.latest_decryption_key_id)
+                    ),
+                    format!("pending puzzles: {}", response.pending_puzzle_count),
+                    format!(
+                        "rounds: {} succeeded / {} failed",
+                        response.rounds_succeeded, response.rounds_failed
+                    ),
+                    format!(
+                        "peers: {} / {} reachable",
+                        response.reachable_peer_count, response.peer_count
+                    ),
+                ];
+
+                for peer in response.peers.iter() {
+                    let last_delivery = match &peer.last_delivery {
+                        Some(last_delivery) => format!(
+                            "{} {}",
+                            last_delivery.method,
+                            last_delivery.outcome.as_str()
+                        ),
+                        None => "-".to_owned(),
+                    };
+
+                    lines.push(format!(
+                        "  {} {} reachable: {} last delivery: {}",
+                        peer.address, peer.cluster_rpc_url, peer.reachable, last_delivery
+                    ));
+                }
+
+                lines.join("\n")
+            })?;
+        }
+        Commands::LatestKey { ref query_option } => {
+            let response = external_client(query_option)?
+                .get_latest_encryption_key()
+                .await?;
+
+            print_response(query_option, &response, || {
+                format!(
+                    "key id: {}\nencryption key: {}",
+                    response.key_id.as_u64(),
+                    response.encryption_key,
+                )
+            })?;
+        }
+        Commands::EncryptionKey {
+            ref query_option,
+            id,
+        } => {
+            let response = external_client(query_option)?
+                .get_encryption_key(KeyId::new(id))
+                .await?;
+
+            print_response(query_option, &response, || {
+                format!(
+                    "key id: {}\nencryption key: {}",
+                    id, response.encryption_key
+                )
+            })?;
+        }
+        Commands::DecryptionKey {
+            ref query_option,
+            id,
+        } => {
+            let response = external_client(query_option)?
+                .get_decryption_key(KeyId::new(id))
+                .await?;
+
+            print_response(query_option, &response, || {
+                format!(
+                    "key id: {}\ndecryption key: {}",
+                    id, response.decryption_key
+                )
+            })?;
+        }
+        Commands::Members { ref query_option } => {
+            let response =
+                ClusterClient::new(query_option.rpc_url.clone(), client_options(query_option)?)?
+                    .get_key_generator_list()
+                    .await?;
+
+            print_response(query_option, &response, || {
+                response
+                    .key_generator_rpc_url_list
+                    .iter()
+                    .map(|info| {
+                        format!(
+                            "{} cluster: {} external: {}",
+                            info.address, info.cluster_rpc_url, info.external_rpc_url
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })?;
+        }
+        Commands::Params { ref query_option } => {
+            let response = external_client(query_option)?.get_skde_params().await?;
+
+            print_response(query_option, &response, || {
+                format!(
+                    "{}\nhash: {}",
+                    serde_json::to_string_pretty(&response.skde_params).unwrap_or_default(),
+                    response.skde_params_hash,
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn external_client(query_option: &QueryOption) -> Result<ExternalClient, Error> {
+    Ok(ExternalClient::new(
+        vec![query_option.rpc_url.clone()],
+        client_options(query_option)?,
+    )?)
+}
+
+fn client_options(query_option: &QueryOption) -> Result<ClientOptions, Error> {
+    let cluster_tls = match (
+        query_option.tls_cert_path.clone(),
+        query_option.tls_key_path.clone(),
+        query_option.ca_path.clone(),
+    ) {
+        (Some(cert_path), Some(key_path), Some(ca_path)) => Some(TlsFiles {
+            cert_path,
+            key_path,
+            ca_path,
+        }),
+        (None, None, _) => None,
+        _ => return Err(ConfigError::IncompleteTlsSettings.into()),
+    };
+
+    Ok(ClientOptions {
+        api_key: query_option.api_key.clone(),
+        ca_path: query_option.ca_path.clone(),
+        cluster_tls,
+        ..ClientOptions::default()
+    })
+}
+
+fn print_response<T: Serialize>(
+    query_option: &QueryOption,
+    response: &T,
+    human_readable: impl FnOnce() -> String,
+) -> Result<(), Error> {
+    if query_option.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(response).map_err(Error::SerializeResponse)?
+        );
+    } else {
+        println!("{}", human_readable());
     }
 
     Ok(())
 }
 
+fn initialize_database(config: &Config) -> Result<(), Error> {
+    database::open(config.database_path())
+        .map_err(error::Error::Database)?
+        .init();
+    database::initialize_models().map_err(error::Error::Database)?;
+
+    tracing::info!(
+        "Successfully initialized the database at {:?}.",
+        config.database_path(),
+    );
+
+    Ok(())
+}
+
 async fn initialize_internal_rpc_server(app_state: &AppState) -> Result<(), Error> {
     let internal_rpc_url = app_state.config().internal_rpc_url().to_string();
 
     // Initialize the internal RPC server.
     let internal_rpc_server = RpcServer::new(app_state.clone())
-        .register_rpc_method::<internal::AddKeyGenerator>()?
+        .register_rpc_method::<Instrumented<internal::AddKeyGenerator>>()?
+        .register_rpc_method::<Instrumented<internal::TriggerRound>>()?
         .init(app_state.config().internal_rpc_url().to_string())
         .await
         .map_err(error::Error::RpcServerError)?;
@@ -169,14 +524,24 @@ async fn initialize_internal_rpc_server(app_state: &AppState) -> Result<(), Erro
 }
 
 async fn initialize_cluster_rpc_server(app_state: &AppState) -> Result<(), Error> {
-    let cluster_rpc_url = anywhere(&app_state.config().cluster_port()?);
+    let cluster_port = app_state.config().cluster_port()?;
+
+    // With mutual TLS the RPC server only listens on the loopback interface
+    // and peers reach it through the gateway, which terminates TLS and checks
+    // that the peer certificate is bound to a registered key generator.
+    let cluster_rpc_url = match app_state.config().cluster_tls() {
+        Some(_) => app_state.config().cluster_upstream_address(),
+        None => anywhere(&cluster_port),
+    };
 
     let key_generator_rpc_server = RpcServer::new(app_state.clone())
-        .register_rpc_method::<cluster::GetKeyGeneratorList>()?
-        .register_rpc_method::<cluster::SyncKeyGenerator>()?
-        .register_rpc_method::<cluster::SyncAggregatedKey>()?
-        .register_rpc_method::<cluster::SyncPartialKey>()?
-        .register_rpc_method::<cluster::RunGeneratePartialKey>()?
+        .register_rpc_method::<Instrumented<cluster::GetKeyGeneratorList>>()?
+        .register_rpc_method::<Instrumented<cluster::Heartbeat>>()?
+        .register_rpc_method::<Instrumented<cluster::SyncKeyGenerator>>()?
+        .register_rpc_method::<Instrumented<cluster::SyncAggregatedKey>>()?
+        .register_rpc_method::<Instrumented<cluster::SyncAggregatedKeySignature>>()?
+        .register_rpc_method::<Instrumented<cluster::SyncPartialKey>>()?
+        .register_rpc_method::<Instrumented<cluster::RunGeneratePartialKey>>()?
         .init(cluster_rpc_url.clone())
         .await
         .map_err(error::Error::RpcServerError)?;
@@ -186,6 +551,19 @@ async fn initialize_cluster_rpc_server(app_state: &AppState) -> Result<(), Error
         cluster_rpc_url
     );
 
+    if let Some(tls_files) = app_state.config().cluster_tls() {
+        run_rpc_gateway(
+            anywhere(&cluster_port),
+            format!("http://{}", cluster_rpc_url),
+            cluster_rpc_limits(),
+            Some(GatewayTls {
+                acceptor: mutual_tls_acceptor(tls_files)?,
+                authorize_peers: true,
+            }),
+        )
+        .await?;
+    }
+
     tokio::spawn(async move {
         key_generator_rpc_server.stopped().await;
     });
@@ -193,16 +571,48 @@ async fn initialize_cluster_rpc_server(app_state: &AppState) -> Result<(), Error
     Ok(())
 }
 
+/// Peers are authenticated by their certificates, so the cluster gateway only
+/// bounds the request size.
+fn cluster_rpc_limits() -> RpcLimits {
+    RpcLimits {
+        rate_limit_per_second: None,
+        rate_limit_burst: 0,
+        max_body_size: 16 * 1024 * 1024,
+        max_concurrent_requests: 1024,
+        max_connections_per_ip: 64,
+        api_keys: Vec::new(),
+    }
+}
+
 async fn initialize_external_rpc_server(app_state: &AppState) -> Result<JoinHandle<()>, Error> {
-    let external_rpc_url = anywhere(&app_state.config().external_port()?);
+    // The RPC server only listens on the loopback interface. Public traffic
+    // goes through the gateway, which enforces the external RPC limits.
+    let external_rpc_url = app_state.config().external_upstream_address();
 
     // Initialize the external RPC server.
-    let external_rpc_server = RpcServer::new(app_state.clone())
-        .register_rpc_method::<external::GetEncryptionKey>()?
-        .register_rpc_method::<external::GetDecryptionKey>()?
-        .register_rpc_method::<external::GetLatestEncryptionKey>()?
-        .register_rpc_method::<external::GetLatestKeyId>()?
-        .register_rpc_method::<external::GetSkdeParams>()?
+    let mut external_rpc_server = RpcServer::new(app_state.clone())
+        .register_rpc_method::<Instrumented<external::GetEncryptionKey>>()?
+        .register_rpc_method::<Instrumented<external::GetBlockRange>>()?
+        .register_rpc_method::<Instrumented<external::GetPublishStatus>>()?
+        .register_rpc_method::<Instrumented<external::GetDecryptionKey>>()?
+        .register_rpc_method::<Instrumented<external::GetEncryptionKeys>>()?
+        .register_rpc_method::<Instrumented<external::GetEncryptionKeyCertificate>>()?
+        .register_rpc_method::<Instrumented<external::GetDecryptionKeys>>()?
+        .register_rpc_method::<Instrumented<external::GetLatestEncryptionKey>>()?
+        .register_rpc_method::<Instrumented<external::GetKeySlotClock>>()?
+        .register_rpc_method::<Instrumented<external::GetLatestKeyId>>()?
+        .register_rpc_method::<Instrumented<external::GetNodeStatus>>()?
+        .register_rpc_method::<Instrumented<external::GetSkdeParams>>()?
+        .register_rpc_method::<Instrumented<external::PollEncryptionKeys>>()?
+        .register_rpc_method::<Instrumented<external::PollDecryptionKeys>>()?;
+
+    if app_state.config().enable_encryption_rpc() {
+        external_rpc_server = external_rpc_server
+            .register_rpc_method::<Instrumented<external::Encrypt>>()?
+            .register_rpc_method::<Instrumented<external::Decrypt>>()?;
+    }
+
+    let external_rpc_server = external_rpc_server
         .init(external_rpc_url.clone())
         .await
         .map_err(error::Error::RpcServerError)?;
@@ -212,6 +622,22 @@ async fn initialize_external_rpc_server(app_state: &AppState) -> Result<JoinHand
         external_rpc_url
     );
 
+    let external_tls = match app_state.config().external_tls() {
+        Some(tls_files) => Some(GatewayTls {
+            acceptor: tls_acceptor(tls_files)?,
+            authorize_peers: false,
+        }),
+        None => None,
+    };
+
+    run_rpc_gateway(
+        anywhere(&app_state.config().external_port()?),
+        format!("http://{}", external_rpc_url),
+        app_state.config().external_rpc_limits().clone(),
+        external_tls,
+    )
+    .await?;
+
     let server_handle = tokio::spawn(async move {
         external_rpc_server.stopped().await;
     });
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use radius_sdk::json_rpc::client::{Id, RpcClient, RpcClientError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const CHAIN_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Minimal Ethereum JSON-RPC client for the few calls the key generator needs
+/// from a chain node.
+///
+/// Requests go through the JSON-RPC client of `radius_sdk`. The SDK's own
+/// Ethereum support (the alloy 0.7 providers of its liveness module) is built
+/// around the Radius liveness contracts, so the registry and commitment
+/// contracts are called through the few `eth_*` methods below instead.
+/// Transactions are still built and signed with the alloy types the SDK
+/// depends on.
+pub struct ChainClient {
+    rpc_client: RpcClient,
+    chain_rpc_url: String,
+}
+
+#[derive(Serialize)]
+struct CallRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<&'a str>,
+    to: &'a str,
+    data: String,
+}
+
+/// The part of a transaction receipt the key generator looks at.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TransactionReceipt {
+    /// `"0x1"` if the transaction succeeded, `"0x0"` if it reverted.
+    pub status: Option<String>,
+}
+
+impl TransactionReceipt {
+    pub fn is_success(&self) -> bool {
+        self.status.as_deref() == Some("0x1")
+    }
+}
+
+impl ChainClient {
+    pub fn new(chain_rpc_url: impl Into<String>) -> Result<Self, ChainError> {
+        Ok(Self {
+            rpc_client: RpcClient::new().map_err(ChainError::RpcClient)?,
+            chain_rpc_url: chain_rpc_url.into(),
+        })
+    }
+
+    pub fn chain_rpc_url(&self) -> &str {
+        &self.chain_rpc_url
+    }
+
+    /// Height of the latest block (`eth_blockNumber`).
+    pub async fn block_number(&self) -> Result<u64, ChainError> {
+        let block_number: String = self.request("eth_blockNumber", ()).await?;
+
+        parse_quantity(&block_number)
+    }
+
+    /// Executes a read-only contract call (`eth_call`) at `block_number`, or at
+    /// the latest block if `None`, and returns the raw return data.
+    pub async fn call(
+        &self,
+        to: &str,
+        data: &[u8],
+        block_number: Option<u64>,
+    ) -> Result<Vec<u8>, ChainError> {
+        let transaction = CallRequest {
+            from: None,
+            to,
+            data: const_hex::encode_prefixed(data),
+        };
+        let block = match block_number {
+            Some(block_number) => format!("{:#x}", block_number),
+            None => "latest".to_owned(),
+        };
+
+        let return_data: String = self.request("eth_call", (transaction, block)).await?;
+
+        const_hex::decode(&return_data).map_err(|_| ChainError::InvalidResponse(return_data))
+    }
+
+    /// Id of the chain, for replay protected transactions (`eth_chainId`).
+    pub async fn chain_id(&self) -> Result<u64, ChainError> {
+        let chain_id: String = self.request("eth_chainId", ()).await?;
+
+        parse_quantity(&chain_id)
+    }
+
+    /// Current gas price in wei (`eth_gasPrice`).
+    pub async fn gas_price(&self) -> Result<u64, ChainError> {
+        let gas_price: String = self.request("eth_gasPrice", ()).await?;
+
+        parse_quantity(&gas_price)
+    }
+
+    /// Gas a transaction from `from` would use (`eth_estimateGas`).
+    pub async fn estimate_gas(&self, from: &str, to: &str, data: &[u8]) -> Result<u64, ChainError> {
+        let transaction = CallRequest {
+            from: Some(from),
+            to,
+            data: const_hex::encode_prefixed(data),
+        };
+
+        let gas: String = self.request("eth_estimateGas", (transaction,)).await?;
+
+        parse_quantity(&gas)
+    }
+
+    /// Number of transactions sent from `address` as of `block` (e.g.
+    /// `"latest"` or `"pending"`), which is the next nonce to use
+    /// (`eth_getTransactionCount`).
+    pub async fn transaction_count(&self, address: &str, block: &str) -> Result<u64, ChainError> {
+        let transaction_count: String = self
+            .request("eth_getTransactionCount", (address, block))
+            .await?;
+
+        parse_quantity(&transaction_count)
+    }
+
+    /// Submits a signed transaction (`eth_sendRawTransaction`) and returns its
+    /// hash.
+    pub async fn send_raw_transaction(&self, raw_transaction: &str) -> Result<String, ChainError> {
+        self.request("eth_sendRawTransaction", (raw_transaction,))
+            .await
+    }
+
+    /// Receipt of the transaction `hash`, or `None` while it is not mined
+    /// (`eth_getTransactionReceipt`).
+    pub async fn transaction_receipt(
+        &self,
+        hash: &str,
+    ) -> Result<Option<TransactionReceipt>, ChainError> {
+        self.request("eth_getTransactionReceipt", (hash,)).await
+    }
+
+    async fn request<P, T>(&self, method: &'static str, params: P) -> Result<T, ChainError>
+    where
+        P: Serialize + Send,
+        T: DeserializeOwned,
+    {
+        tokio::time::timeout(
+            CHAIN_REQUEST_TIMEOUT,
+            self.rpc_client
+                .request(&self.chain_rpc_url, method, &params, Id::Null),
+        )
+        .await
+        .map_err(|_| ChainError::Timeout)?
+        .map_err(ChainError::RpcClient)
+    }
+}
+
+/// Parses a hex encoded JSON-RPC quantity such as `"0x1b4"`.
+pub fn parse_quantity(quantity: &str) -> Result<u64, ChainError> {
+    let digits = quantity
+        .strip_prefix("0x")
+        .ok_or_else(|| ChainError::InvalidResponse(quantity.to_owned()))?;
+
+    u64::from_str_radix(digits, 16).map_err(|_| ChainError::InvalidResponse(quantity.to_owned()))
+}
+
+#[derive(Debug)]
+pub enum ChainError {
+    RpcClient(RpcClientError),
+    Timeout,
+    InvalidResponse(String),
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ChainError {}
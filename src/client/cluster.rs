@@ -0,0 +1,70 @@
+use super::{ClientError, ClientOptions, Transport};
+use crate::rpc::cluster::*;
+
+/// Typed client for the cluster RPC API. Reads fail over across every given
+/// cluster RPC url; the `sync_*` and `run_*` methods are sent once to the
+/// first one. Requests go over mutual TLS when
+/// [`ClientOptions::cluster_tls`] is set.
+pub struct ClusterClient {
+    transport: Transport,
+}
+
+impl ClusterClient {
+    pub fn new(
+        cluster_rpc_url: impl Into<String>,
+        options: ClientOptions,
+    ) -> Result<Self, ClientError> {
+        Self::with_rpc_urls(vec![cluster_rpc_url.into()], options)
+    }
+
+    pub fn with_rpc_urls(
+        cluster_rpc_urls: Vec<String>,
+        options: ClientOptions,
+    ) -> Result<Self, ClientError> {
+        Ok(Self {
+            transport: Transport::cluster(cluster_rpc_urls, options)?,
+        })
+    }
+
+    pub async fn get_key_generator_list(
+        &self,
+    ) -> Result<GetKeyGeneratorRpcUrlListResponse, ClientError> {
+        self.transport.request(&GetKeyGeneratorList).await
+    }
+
+    pub async fn heartbeat(&self, parameter: &Heartbeat) -> Result<HeartbeatResponse, ClientError> {
+        self.transport.request(parameter).await
+    }
+
+    pub async fn sync_key_generator(
+        &self,
+        parameter: &SyncKeyGenerator,
+    ) -> Result<(), ClientError> {
+        self.transport.send(parameter).await
+    }
+
+    pub async fn sync_partial_key(&self, parameter: &SyncPartialKey) -> Result<(), ClientError> {
+        self.transport.send(parameter).await
+    }
+
+    pub async fn sync_aggregated_key(
+        &self,
+        parameter: &SyncAggregatedKey,
+    ) -> Result<(), ClientError> {
+        self.transport.send(parameter).await
+    }
+
+    pub async fn sync_aggregated_key_signature(
+        &self,
+        parameter: &SyncAggregatedKeySignature,
+    ) -> Result<(), ClientError> {
+        self.transport.send(parameter).await
+    }
+
+    pub async fn run_generate_partial_key(
+        &self,
+        parameter: &RunGeneratePartialKey,
+    ) -> Result<(), ClientError> {
+        self.transport.send(parameter).await
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use super::{ClientError, ClientOptions, Transport};
+use crate::{
+    client::ClusterClient,
+    rpc::external::*,
+    types::{KeyGeneratorList, KeyId},
+};
+
+/// Typed client for the external RPC API. Requests fail over across every
+/// given external RPC url.
+pub struct ExternalClient {
+    transport: Transport,
+}
+
+impl ExternalClient {
+    pub fn new(
+        external_rpc_urls: Vec<String>,
+        options: ClientOptions,
+    ) -> Result<Self, ClientError> {
+        Ok(Self {
+            transport: Transport::new(external_rpc_urls, options)?,
+        })
+    }
+
+    /// Builds a client over the external RPC urls of every key generator
+    /// known to the node behind `cluster_rpc_url`.
+    pub async fn from_cluster(
+        cluster_rpc_url: impl Into<String>,
+        options: ClientOptions,
+    ) -> Result<Self, ClientError> {
+        let cluster_client = ClusterClient::new(cluster_rpc_url, options.clone())?;
+        let key_generator_list = KeyGeneratorList::try_from(
+            cluster_client
+                .get_key_generator_list()
+                .await?
+                .key_generator_rpc_url_list,
+        )
+        .map_err(ClientError::InvalidKeyGeneratorAddress)?;
+
+        let external_rpc_urls = key_generator_list
+            .iter()
+            .map(|key_generator| key_generator.external_rpc_url().to_owned())
+            .collect();
+
+        Self::new(external_rpc_urls, options)
+    }
+
+    pub async fn get_encryption_key(
+        &self,
+        key_id: KeyId,
+    ) -> Result<GetEncryptionKeyResponse, ClientError> {
+        self.transport.request(&GetEncryptionKey { key_id }).await
+    }
+
+    pub async fn get_decryption_key(
+        &self,
+        key_id: KeyId,
+    ) -> Result<GetDecryptionKeyResponse, ClientError> {
+        self.transport.request(&GetDecryptionKey { key_id }).await
+    }
+
+    pub async fn get_latest_encryption_key(
+        &self,
+    ) -> Result<GetLatestEncryptionKeyResponse, ClientError> {
+        self.transport.request(&GetLatestEncryptionKey {}).await
+    }
+
+    pub async fn get_block_range(
+        &self,
+        key_id: KeyId,
+    ) -> Result<GetBlockRangeResponse, ClientError> {
+        self.transport.request(&GetBlockRange { key_id }).await
+    }
+
+    pub async fn get_key_slot_clock(&self) -> Result<GetKeySlotClockResponse, ClientError> {
+        self.transport.request(&GetKeySlotClock {}).await
+    }
+
+    pub async fn get_latest_key_id(&self) -> Result<GetLatestKeyIdResponse, ClientError> {
+        self.transport.request(&GetLatestKeyId {}).await
+    }
+
+    pub async fn get_node_status(&self) -> Result<GetNodeStatusResponse, ClientError> {
+        self.transport.request(&GetNodeStatus {}).await
+    }
+
+    pub async fn get_publish_status(
+        &self,
+        key_id: KeyId,
+    ) -> Result<GetPublishStatusResponse, ClientError> {
+        self.transport.request(&GetPublishStatus { key_id }).await
+    }
+
+    pub async fn get_skde_params(&self) -> Result<GetSkdeParamsResponse, ClientError> {
+        self.transport.request(&GetSkdeParams {}).await
+    }
+
+    pub async fn get_encryption_keys(
+        &self,
+        from_key_id: KeyId,
+        to_key_id: KeyId,
+    ) -> Result<GetEncryptionKeysResponse, ClientError> {
+        self.transport
+            .request(&GetEncryptionKeys {
+                from_key_id,
+                to_key_id,
+            })
+            .await
+    }
+
+    pub async fn get_decryption_keys(
+        &self,
+        from_key_id: KeyId,
+        to_key_id: KeyId,
+    ) -> Result<GetDecryptionKeysResponse, ClientError> {
+        self.transport
+            .request(&GetDecryptionKeys {
+                from_key_id,
+                to_key_id,
+            })
+            .await
+    }
+
+    pub async fn get_encryption_key_certificate(
+        &self,
+        key_id: KeyId,
+    ) -> Result<GetEncryptionKeyCertificateResponse, ClientError> {
+        self.transport
+            .request(&GetEncryptionKeyCertificate { key_id })
+            .await
+    }
+
+    /// Only served by nodes started with `enable_encryption_rpc`.
+    pub async fn encrypt(
+        &self,
+        key_id: KeyId,
+        plaintext: String,
+    ) -> Result<EncryptResponse, ClientError> {
+        self.transport.request(&Encrypt { key_id, plaintext }).await
+    }
+
+    /// Only served by nodes started with `enable_encryption_rpc`.
+    pub async fn decrypt(
+        &self,
+        key_id: KeyId,
+        ciphertext: String,
+    ) -> Result<DecryptResponse, ClientError> {
+        self.transport
+            .request(&Decrypt { key_id, ciphertext })
+            .await
+    }
+
+    /// The request timeout is extended by `wait` so that the server can hold
+    /// the request for as long as asked.
+    pub async fn poll_encryption_keys(
+        &self,
+        from_key_id: KeyId,
+        wait: Duration,
+    ) -> Result<PollEncryptionKeysResponse, ClientError> {
+        let parameter = PollEncryptionKeys {
+            from_key_id,
+            timeout_ms: Some(wait.as_millis() as u64),
+        };
+
+        self.transport
+            .request_with_timeout(&parameter, self.poll_timeout(wait))
+            .await
+    }
+
+    pub async fn poll_decryption_keys(
+        &self,
+        from_key_id: KeyId,
+        wait: Duration,
+    ) -> Result<PollDecryptionKeysResponse, ClientError> {
+        let parameter = PollDecryptionKeys {
+            from_key_id,
+            timeout_ms: Some(wait.as_millis() as u64),
+        };
+
+        self.transport
+            .request_with_timeout(&parameter, self.poll_timeout(wait))
+            .await
+    }
+
+    fn poll_timeout(&self, wait: Duration) -> Duration {
+        self.transport.options.request_timeout + wait
+    }
+}
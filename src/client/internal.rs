@@ -0,0 +1,39 @@
+use radius_sdk::signature::Address;
+
+use super::{ClientError, ClientOptions, Transport};
+use crate::rpc::internal::*;
+
+/// Typed client for the internal RPC API of a single node.
+pub struct InternalClient {
+    transport: Transport,
+}
+
+impl InternalClient {
+    pub fn new(
+        internal_rpc_url: impl Into<String>,
+        options: ClientOptions,
+    ) -> Result<Self, ClientError> {
+        Ok(Self {
+            transport: Transport::new(vec![internal_rpc_url.into()], options)?,
+        })
+    }
+
+    pub async fn add_key_generator(
+        &self,
+        address: Address,
+        cluster_rpc_url: String,
+        external_rpc_url: String,
+    ) -> Result<(), ClientError> {
+        self.transport
+            .send(&AddKeyGenerator::new(
+                address,
+                cluster_rpc_url,
+                external_rpc_url,
+            ))
+            .await
+    }
+
+    pub async fn trigger_round(&self) -> Result<(), ClientError> {
+        self.transport.send(&TriggerRound {}).await
+    }
+}
@@ -0,0 +1,224 @@
+mod cluster;
+mod external;
+mod internal;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+pub use cluster::*;
+pub use external::*;
+pub use internal::*;
+use radius_sdk::json_rpc::server::RpcParameter;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Certificate,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    rpc::transport::{self, ClusterTransport, JsonRpcTransport, TlsTransport, TransportError},
+    state::AppState,
+    tls::TlsError,
+    types::TlsFiles,
+};
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug)]
+pub struct ClientOptions {
+    /// Timeout applied to each individual request.
+    pub request_timeout: Duration,
+    /// How many more times the whole endpoint list is tried after the first
+    /// round fails.
+    pub max_retries: u32,
+    /// Delay between two rounds over the endpoint list.
+    pub retry_interval: Duration,
+    /// Sent in the `x-api-key` header to external and internal RPC servers.
+    pub api_key: Option<String>,
+    /// PEM CA bundle trusted, besides the built-in roots, by the external
+    /// and internal RPC clients, e.g. for an external port serving a
+    /// private certificate.
+    pub ca_path: Option<PathBuf>,
+    /// Certificate, key and cluster CA presented to cluster RPC servers that
+    /// require mutual TLS.
+    pub cluster_tls: Option<TlsFiles>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_interval: DEFAULT_RETRY_INTERVAL,
+            api_key: None,
+            ca_path: None,
+            cluster_tls: None,
+        }
+    }
+}
+
+/// Sends typed requests to a list of equivalent endpoints. Reads fail over to
+/// the next endpoint whenever one errors or times out; writes are sent once,
+/// since a request that timed out may still have been applied.
+struct Transport {
+    sender: Box<dyn ClusterTransport>,
+    rpc_urls: Vec<String>,
+    options: ClientOptions,
+}
+
+impl Transport {
+    /// Sends JSON-RPC over HTTP or HTTPS with the api key and CA bundle of
+    /// `options`.
+    fn new(rpc_urls: Vec<String>, options: ClientOptions) -> Result<Self, ClientError> {
+        let sender = JsonRpcTransport::with_http_client(http_client(&options)?);
+
+        Self::with_sender(rpc_urls, options, Box::new(sender))
+    }
+
+    /// Like [`Transport::new`], but over mutual TLS when `options` set
+    /// [`ClientOptions::cluster_tls`], the way key generators reach each
+    /// other.
+    fn cluster(rpc_urls: Vec<String>, options: ClientOptions) -> Result<Self, ClientError> {
+        match options.cluster_tls.as_ref() {
+            Some(tls_files) => {
+                let sender = TlsTransport::new(tls_files).map_err(ClientError::Tls)?;
+
+                Self::with_sender(rpc_urls, options, Box::new(sender))
+            }
+            None => Self::new(rpc_urls, options),
+        }
+    }
+
+    fn with_sender(
+        rpc_urls: Vec<String>,
+        options: ClientOptions,
+        sender: Box<dyn ClusterTransport>,
+    ) -> Result<Self, ClientError> {
+        if rpc_urls.is_empty() {
+            return Err(ClientError::NoEndpoint);
+        }
+
+        Ok(Self {
+            sender,
+            rpc_urls,
+            options,
+        })
+    }
+
+    /// Sends a read, which is safe to repeat.
+    async fn request<P>(&self, parameter: &P) -> Result<P::Response, ClientError>
+    where
+        P: RpcParameter<AppState>,
+        P::Response: DeserializeOwned,
+    {
+        self.request_with_timeout(parameter, self.options.request_timeout)
+            .await
+    }
+
+    async fn request_with_timeout<P>(
+        &self,
+        parameter: &P,
+        request_timeout: Duration,
+    ) -> Result<P::Response, ClientError>
+    where
+        P: RpcParameter<AppState>,
+        P::Response: DeserializeOwned,
+    {
+        let mut last_error = ClientError::NoEndpoint;
+
+        for attempt in 0..=self.options.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(self.options.retry_interval).await;
+            }
+
+            for rpc_url in self.rpc_urls.iter() {
+                match self.send_to(rpc_url, parameter, request_timeout).await {
+                    Ok(response) => return Ok(response),
+                    Err(error) => last_error = error,
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Sends a write to the first endpoint only, without retrying.
+    async fn send<P>(&self, parameter: &P) -> Result<P::Response, ClientError>
+    where
+        P: RpcParameter<AppState>,
+        P::Response: DeserializeOwned,
+    {
+        self.send_to(&self.rpc_urls[0], parameter, self.options.request_timeout)
+            .await
+    }
+
+    async fn send_to<P>(
+        &self,
+        rpc_url: &str,
+        parameter: &P,
+        request_timeout: Duration,
+    ) -> Result<P::Response, ClientError>
+    where
+        P: RpcParameter<AppState>,
+        P::Response: DeserializeOwned,
+    {
+        let response = tokio::time::timeout(
+            request_timeout,
+            transport::request(self.sender.as_ref(), rpc_url, parameter),
+        )
+        .await;
+
+        match response {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(error)) => {
+                tracing::debug!("{} failed at {}: {}", P::method(), rpc_url, error);
+                Err(ClientError::Transport(error))
+            }
+            Err(_) => {
+                tracing::debug!("{} timed out at {}", P::method(), rpc_url);
+                Err(ClientError::Timeout)
+            }
+        }
+    }
+}
+
+fn http_client(options: &ClientOptions) -> Result<reqwest::Client, ClientError> {
+    let mut headers = HeaderMap::new();
+    if let Some(api_key) = options.api_key.as_ref() {
+        let api_key = HeaderValue::from_str(api_key).map_err(|_| ClientError::InvalidApiKey)?;
+        headers.insert("x-api-key", api_key);
+    }
+
+    let mut client_builder = reqwest::Client::builder().default_headers(headers);
+    if let Some(ca_path) = options.ca_path.as_ref() {
+        let ca_pem = fs::read(ca_path).map_err(|error| ClientError::Tls(TlsError::Io(error)))?;
+        for ca_certificate in Certificate::from_pem_bundle(&ca_pem)
+            .map_err(|error| ClientError::Tls(TlsError::Http(error)))?
+        {
+            client_builder = client_builder.add_root_certificate(ca_certificate);
+        }
+    }
+
+    client_builder
+        .build()
+        .map_err(|error| ClientError::Transport(TransportError::Http(error)))
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Transport(TransportError),
+    Tls(TlsError),
+    Timeout,
+    NoEndpoint,
+    InvalidApiKey,
+    InvalidKeyGeneratorAddress(const_hex::FromHexError),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ClientError {}
@@ -0,0 +1,155 @@
+use std::{future::Future, path::Path, sync::Mutex};
+
+use radius_sdk::kvstore::{kvstore, KvStore, KvStoreBuilder, KvStoreError};
+use tokio::task::JoinHandle;
+
+use crate::types::{KeyGeneratorList, KeyId, KeyPublishQueue, LatestAggregatedKeyId, WebhookQueue};
+
+/// Held while a model checks whether a value exists and creates it, so that
+/// two callers never both create it (see `apply_or` of [`model!`]).
+pub(crate) static CREATE_LOCK: Mutex<()> = Mutex::new(());
+
+tokio::task_local! {
+    static SCOPED_DATABASE: &'static KvStore;
+}
+
+/// Opens (or creates) the database at `path`.
+pub fn open(path: impl AsRef<Path>) -> Result<KvStore, KvStoreError> {
+    KvStoreBuilder::default()
+        .set_default_lock_timeout(5000)
+        .set_txn_lock_timeout(5000)
+        .build(path.as_ref())
+}
+
+/// Creates the models every node expects to exist.
+pub fn initialize_models() -> Result<(), KvStoreError> {
+    KeyGeneratorList::initialize()?;
+    KeyId::initialize()?;
+    LatestAggregatedKeyId::initialize()?;
+    WebhookQueue::initialize()?;
+    KeyPublishQueue::initialize()?;
+
+    Ok(())
+}
+
+/// The database models are read from and written to: the one of the current
+/// [`scope`], or else the database of the process, which is the one
+/// `KvStore::init` set up for the models of radius_sdk.
+pub fn database() -> Result<&'static KvStore, KvStoreError> {
+    match SCOPED_DATABASE.try_with(|database| *database) {
+        Ok(database) => Ok(database),
+        Err(_) => kvstore(),
+    }
+}
+
+/// Runs `future` against `database` instead of the database of the process,
+/// so that several nodes can live in one process (e.g. in tests).
+pub async fn scope<F: Future>(database: &'static KvStore, future: F) -> F::Output {
+    SCOPED_DATABASE.scope(database, future).await
+}
+
+/// Like [`scope`], for synchronous code.
+pub fn sync_scope<F, R>(database: &'static KvStore, function: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    SCOPED_DATABASE.sync_scope(database, function)
+}
+
+/// Wraps `future` so that it keeps the database of the current [`scope`],
+/// if any, when it is polled from another task.
+pub fn in_current_scope<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let database = SCOPED_DATABASE.try_with(|database| *database).ok();
+
+    async move {
+        match database {
+            Some(database) => SCOPED_DATABASE.scope(database, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Spawns `future` on the runtime, keeping the database of the current
+/// [`scope`].
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(in_current_scope(future))
+}
+
+/// Implements the persistence methods of a model stored under
+/// `(model name, key...)` in [`database()`], the key layout of the `Model`
+/// derive of radius_sdk.
+macro_rules! model {
+    ($name:ident, key($($key:ident: $key_type:ty),*)) => {
+        impl $name {
+            pub fn get($($key: $key_type),*) -> Result<Self, ::radius_sdk::kvstore::KvStoreError> {
+                $crate::database::database()?.get(&(stringify!($name), $($key),*))
+            }
+
+            pub fn get_or<F>(
+                $($key: $key_type,)*
+                function: F,
+            ) -> Result<Self, ::radius_sdk::kvstore::KvStoreError>
+            where
+                F: FnOnce() -> Self,
+            {
+                $crate::database::database()?.get_or(&(stringify!($name), $($key),*), function)
+            }
+
+            pub fn get_mut(
+                $($key: $key_type),*
+            ) -> Result<::radius_sdk::kvstore::Lock<'static, Self>, ::radius_sdk::kvstore::KvStoreError> {
+                $crate::database::database()?.get_mut(&(stringify!($name), $($key),*))
+            }
+
+            pub fn put(&self, $($key: $key_type),*) -> Result<(), ::radius_sdk::kvstore::KvStoreError> {
+                $crate::database::database()?.put(&(stringify!($name), $($key),*), self)
+            }
+
+            pub fn apply<F>(
+                $($key: $key_type,)*
+                operation: F,
+            ) -> Result<(), ::radius_sdk::kvstore::KvStoreError>
+            where
+                F: FnOnce(&mut Self),
+            {
+                let mut value = Self::get_mut($($key),*)?;
+                operation(&mut value);
+                value.update()
+            }
+
+            /// Like `apply`, creating the value with `function` first if it
+            /// is absent. The value is created at most once, so updates of
+            /// concurrent callers are never overwritten.
+            pub fn apply_or<F, O>(
+                $($key: $key_type,)*
+                function: F,
+                operation: O,
+            ) -> Result<(), ::radius_sdk::kvstore::KvStoreError>
+            where
+                F: FnOnce() -> Self,
+                O: FnOnce(&mut Self),
+            {
+                {
+                    let _creating = $crate::database::CREATE_LOCK
+                        .lock()
+                        .unwrap_or_else(|error| error.into_inner());
+                    if Self::get($($key),*).is_err() {
+                        function().put($($key),*)?;
+                    }
+                }
+
+                Self::apply($($key,)* operation)
+            }
+
+            pub fn delete($($key: $key_type),*) -> Result<(), ::radius_sdk::kvstore::KvStoreError> {
+                $crate::database::database()?.delete(&(stringify!($name), $($key),*))
+            }
+        }
+    };
+}
+
+pub(crate) use model;
@@ -0,0 +1,26 @@
+use skde::delay_encryption::{decrypt as skde_decrypt, encrypt as skde_encrypt, SkdeParams};
+
+use crate::{error::Error, types::*};
+
+/// Encrypts `plaintext` with the aggregated encryption key stored for
+/// `key_id`. Meant for integration tests and clients without a native SKDE
+/// binding; production clients should encrypt locally.
+pub fn encrypt(skde_params: &SkdeParams, key_id: KeyId, plaintext: &str) -> Result<String, Error> {
+    let encryption_key = AggregatedKey::get(key_id)
+        .map_err(|_| Error::EncryptionKeyNotReady)?
+        .encryption_key();
+
+    skde_encrypt(skde_params, plaintext, &encryption_key)
+        .map_err(|error| Error::Encryption(format!("{:?}", error)))
+}
+
+/// Decrypts `ciphertext` with the decryption key stored for `key_id`, which
+/// only exists once the time-lock puzzle for that key has been solved.
+pub fn decrypt(skde_params: &SkdeParams, key_id: KeyId, ciphertext: &str) -> Result<String, Error> {
+    let decryption_key = DecryptionKey::get(key_id)
+        .map_err(|_| Error::DecryptionKeyNotReady)?
+        .as_string();
+
+    skde_decrypt(skde_params, ciphertext, &decryption_key)
+        .map_err(|error| Error::Decryption(format!("{:?}", error)))
+}
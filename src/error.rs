@@ -1,4 +1,4 @@
-use radius_sdk::kvstore::{KvStoreError};
+use radius_sdk::kvstore::KvStoreError;
 
 #[derive(Debug)]
 pub enum Error {
@@ -6,6 +6,21 @@ pub enum Error {
     Database(radius_sdk::kvstore::KvStoreError),
     RpcServerError(radius_sdk::json_rpc::server::RpcServerError),
     RpcClientError(radius_sdk::json_rpc::client::RpcClientError),
+    Archive(crate::archive::ArchiveError),
+    Signature(radius_sdk::signature::SignatureError),
+    Webhook(reqwest::Error),
+    Client(crate::client::ClientError),
+    Chain(crate::chain::ChainError),
+    Publisher(crate::publisher::PublishError),
+    SerializeResponse(serde_json::Error),
+    SerializeSkdeParams(serde_json::Error),
+    HealthServer(std::io::Error),
+    RpcGateway(std::io::Error),
+    Tls(crate::tls::TlsError),
+    Transport(crate::rpc::transport::TransportError),
+    LogFilter(tracing_subscriber::filter::ParseError),
+    InitializeLogger(Box<dyn std::error::Error + Send + Sync>),
+    InvalidLogFilePath,
 
     LoadConfigOption(std::io::Error),
     ParseTomlString(toml::de::Error),
@@ -16,6 +31,25 @@ pub enum Error {
     HexDecodeError,
 
     NotFound,
+    EncryptionKeyNotReady,
+    DecryptionKeyNotReady,
+    Encryption(String),
+    Decryption(String),
+    InvalidKeyIdRange,
+    NoPartialKey,
+    /// A participant of the leader's aggregated key whose partial key this
+    /// node does not have.
+    MissingPartialKey(String),
+    /// The leader's participants aggregate to another key here.
+    AggregatedKeyMismatch,
+    SolveTimeLockPuzzle(String),
+    JoinTask(tokio::task::JoinError),
+    BeforeGenesis,
+    KeyIdAlreadyUsed,
+    /// Membership follows the registry contract and cannot be changed by RPC.
+    MembershipManagedByRegistry,
+    EmptyRegistry,
+    NotInRegistry,
 }
 
 unsafe impl Send for Error {}
@@ -51,3 +85,45 @@ impl From<radius_sdk::json_rpc::client::RpcClientError> for Error {
         Self::RpcClientError(value)
     }
 }
+
+impl From<crate::archive::ArchiveError> for Error {
+    fn from(value: crate::archive::ArchiveError) -> Self {
+        Self::Archive(value)
+    }
+}
+
+impl From<radius_sdk::signature::SignatureError> for Error {
+    fn from(value: radius_sdk::signature::SignatureError) -> Self {
+        Self::Signature(value)
+    }
+}
+
+impl From<crate::client::ClientError> for Error {
+    fn from(value: crate::client::ClientError) -> Self {
+        Self::Client(value)
+    }
+}
+
+impl From<crate::chain::ChainError> for Error {
+    fn from(value: crate::chain::ChainError) -> Self {
+        Self::Chain(value)
+    }
+}
+
+impl From<crate::publisher::PublishError> for Error {
+    fn from(value: crate::publisher::PublishError) -> Self {
+        Self::Publisher(value)
+    }
+}
+
+impl From<crate::tls::TlsError> for Error {
+    fn from(value: crate::tls::TlsError) -> Self {
+        Self::Tls(value)
+    }
+}
+
+impl From<crate::rpc::transport::TransportError> for Error {
+    fn from(value: crate::rpc::transport::TransportError) -> Self {
+        Self::Transport(value)
+    }
+}
@@ -0,0 +1,87 @@
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{database, error::Error, metrics, state::AppState, types::*};
+
+/// Serves plain HTTP `GET /health`, `GET /ready` and `GET /metrics` for load
+/// balancers, orchestrators and scrapers that cannot speak JSON-RPC.
+///
+/// - `/health` answers `200` as long as the process accepts connections.
+/// - `/ready` answers `200` once the node is registered in its key generator
+///   list and an encryption key can be served, `503` otherwise.
+/// - `/metrics` renders the Prometheus metrics of the node.
+pub async fn run_health_server(context: AppState, listen_address: String) -> Result<(), Error> {
+    let listener = TcpListener::bind(&listen_address)
+        .await
+        .map_err(Error::HealthServer)?;
+
+    tracing::info!(
+        "Successfully started the health check server: {}",
+        listen_address
+    );
+
+    database::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    tracing::warn!("Failed to accept a health check connection: {}", error);
+                    continue;
+                }
+            };
+
+            let context = context.clone();
+            database::spawn(async move {
+                if let Err(error) = handle_connection(&context, stream).await {
+                    tracing::debug!("Health check connection failed: {}", error);
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+async fn handle_connection(context: &AppState, mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buffer = [0; 1024];
+    let length = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..length]);
+
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let (status, body) = match (method, path) {
+        ("GET", "/health") => ("200 OK", "ok".to_owned()),
+        ("GET", "/ready") if is_ready(context) => ("200 OK", "ready".to_owned()),
+        ("GET", "/ready") => ("503 Service Unavailable", "not ready".to_owned()),
+        ("GET", "/metrics") => ("200 OK", metrics::render()),
+        _ => ("404 Not Found", "not found".to_owned()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn is_ready(context: &AppState) -> bool {
+    let is_registered = KeyGeneratorList::get()
+        .map(|key_generator_list| {
+            key_generator_list.is_key_generator_in_cluster(context.config().address())
+        })
+        .unwrap_or(false);
+
+    is_registered && LatestAggregatedKeyId::get().is_ok()
+}
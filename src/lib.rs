@@ -1,6 +1,17 @@
+pub mod archive;
+pub mod chain;
+pub mod client;
+pub mod database;
+pub mod encryption;
 pub mod error;
+pub mod health;
+pub mod logger;
+pub mod metrics;
+pub mod publisher;
+pub mod registry;
 pub mod rpc;
 pub mod state;
 pub mod task;
+pub mod tls;
 pub mod types;
 pub mod util;
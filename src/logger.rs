@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{fmt::writer::BoxMakeWriter, EnvFilter};
+
+use crate::{error::Error, types::*};
+
+static LOG_SECRETS: AtomicBool = AtomicBool::new(false);
+
+/// Whether secret material (decryption keys, signing keys) may be written to
+/// the logs. Off unless `log_secrets` is set.
+pub fn log_secrets() -> bool {
+    LOG_SECRETS.load(Ordering::Relaxed)
+}
+
+/// Formats secret material for logging, honoring [`log_secrets`].
+pub struct Secret<'a>(pub &'a str);
+
+impl std::fmt::Debug for Secret<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if log_secrets() {
+            write!(f, "{:?}", self.0)
+        } else {
+            write!(f, "<redacted>")
+        }
+    }
+}
+
+/// Installs the global subscriber described by the logging fields of
+/// `config`. The returned guard flushes the log file on drop and must be held
+/// for as long as the node runs.
+pub fn init_logger(config: &Config) -> Result<Option<WorkerGuard>, Error> {
+    LOG_SECRETS.store(config.log_secrets(), Ordering::Relaxed);
+
+    let filter = EnvFilter::try_new(config.log_level()).map_err(Error::LogFilter)?;
+
+    let (writer, guard) = match config.log_file_path() {
+        Some(log_file_path) => {
+            let rotation = match config.log_rotation() {
+                LogRotation::Minutely => Rotation::MINUTELY,
+                LogRotation::Hourly => Rotation::HOURLY,
+                LogRotation::Daily => Rotation::DAILY,
+                LogRotation::Never => Rotation::NEVER,
+            };
+            let directory = log_file_path
+                .parent()
+                .map(|parent| parent.to_path_buf())
+                .unwrap_or_default();
+            let file_name = log_file_path.file_name().ok_or(Error::InvalidLogFilePath)?;
+
+            let appender = RollingFileAppender::new(rotation, directory, file_name);
+            let (non_blocking, guard) = tracing_appender::non_blocking(appender);
+
+            (BoxMakeWriter::new(non_blocking), Some(guard))
+        }
+        None => (BoxMakeWriter::new(std::io::stdout), None),
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+
+    match config.log_format() {
+        LogFormat::Pretty => builder.try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .try_init(),
+    }
+    .map_err(Error::InitializeLogger)?;
+
+    Ok(guard)
+}
+
+/// Logger for commands that run without a node configuration. Writes to
+/// stderr so that command output on stdout stays machine-readable.
+pub fn init_default_logger() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+}
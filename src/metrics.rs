@@ -0,0 +1,178 @@
+use std::{sync::LazyLock, time::Duration};
+
+use prometheus::{
+    register_histogram, register_histogram_vec, register_int_counter_vec, register_int_gauge,
+    Encoder, Histogram, HistogramVec, IntCounterVec, IntGauge, TextEncoder,
+};
+
+static PARTIAL_KEYS_RECEIVED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_partial_keys_received_total",
+        "Partial keys accepted per peer",
+        &["peer"]
+    )
+    .unwrap()
+});
+
+static PARTIAL_KEYS_REJECTED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_partial_keys_rejected_total",
+        "Partial keys rejected per peer and reason",
+        &["peer", "reason"]
+    )
+    .unwrap()
+});
+
+static AGGREGATION_DURATION: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "dkg_aggregation_duration_seconds",
+        "Time spent aggregating the partial keys of a key id"
+    )
+    .unwrap()
+});
+
+static AGGREGATION_PARTICIPANTS: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "dkg_aggregation_participants",
+        "Number of partial keys aggregated per key id",
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 8.0, 16.0, 32.0, 64.0]
+    )
+    .unwrap()
+});
+
+static LATEST_AGGREGATED_KEY_ID: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!(
+        "dkg_latest_aggregated_key_id",
+        "Highest key id aggregated by this node"
+    )
+    .unwrap()
+});
+
+static TIME_TO_DECRYPTION_KEY: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "dkg_time_to_decryption_key_seconds",
+        "Time from aggregating a key to solving its time-lock puzzle",
+        vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0]
+    )
+    .unwrap()
+});
+
+static RPC_REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_rpc_requests_total",
+        "RPC requests handled per method and status",
+        &["method", "status"]
+    )
+    .unwrap()
+});
+
+static RPC_REQUEST_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "dkg_rpc_request_duration_seconds",
+        "RPC handler latency per method",
+        &["method"]
+    )
+    .unwrap()
+});
+
+static RPC_REJECTIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_rpc_rejections_total",
+        "External RPC requests refused by the gateway per reason",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+static MULTICAST_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_multicast_failures_total",
+        "Peers a cluster multicast could not be delivered to, per method",
+        &["method"]
+    )
+    .unwrap()
+});
+
+static MULTICAST_DELIVERIES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_multicast_deliveries_total",
+        "Multicast deliveries per method and final outcome",
+        &["method", "outcome"]
+    )
+    .unwrap()
+});
+
+static ROUNDS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "dkg_rounds_total",
+        "Key generation rounds driven by this node per result",
+        &["result"]
+    )
+    .unwrap()
+});
+
+pub fn record_partial_key_received(peer: &str) {
+    PARTIAL_KEYS_RECEIVED.with_label_values(&[peer]).inc();
+}
+
+/// `peer` label of partial keys from addresses outside the cluster, which
+/// would otherwise let anyone create new series.
+pub const UNKNOWN_PEER: &str = "unknown";
+
+pub fn record_partial_key_rejected(peer: &str, reason: &str) {
+    PARTIAL_KEYS_REJECTED
+        .with_label_values(&[peer, reason])
+        .inc();
+}
+
+pub fn record_aggregation(key_id: u64, participant_count: usize, duration: Duration) {
+    AGGREGATION_DURATION.observe(duration.as_secs_f64());
+    AGGREGATION_PARTICIPANTS.observe(participant_count as f64);
+
+    if key_id as i64 > LATEST_AGGREGATED_KEY_ID.get() {
+        LATEST_AGGREGATED_KEY_ID.set(key_id as i64);
+    }
+}
+
+pub fn record_time_to_decryption_key(duration: Duration) {
+    TIME_TO_DECRYPTION_KEY.observe(duration.as_secs_f64());
+}
+
+pub fn record_rpc_request(method: &str, is_ok: bool, duration: Duration) {
+    let status = if is_ok { "ok" } else { "error" };
+
+    RPC_REQUESTS.with_label_values(&[method, status]).inc();
+    RPC_REQUEST_DURATION
+        .with_label_values(&[method])
+        .observe(duration.as_secs_f64());
+}
+
+pub fn record_rpc_rejection(reason: &str) {
+    RPC_REJECTIONS.with_label_values(&[reason]).inc();
+}
+
+pub fn record_multicast_failure(method: &str) {
+    MULTICAST_FAILURES.with_label_values(&[method]).inc();
+}
+
+pub fn record_multicast_delivery(method: &str, outcome: &str) {
+    MULTICAST_DELIVERIES
+        .with_label_values(&[method, outcome])
+        .inc();
+}
+
+pub fn record_round(is_ok: bool) {
+    let result = if is_ok { "ok" } else { "error" };
+
+    ROUNDS.with_label_values(&[result]).inc();
+}
+
+/// Renders every registered metric in the Prometheus text exposition format.
+pub fn render() -> String {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .unwrap_or_default();
+
+    String::from_utf8(buffer).unwrap_or_default()
+}
@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use alloy_consensus::{SignableTransaction, TxEnvelope, TxLegacy};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network::TxSignerSync;
+use alloy_primitives::{Address as EthereumAddress, Bytes, TxKind, B256, U256};
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{sol, SolCall};
+
+use super::{KeyPublisher, PublishError, PublishFuture};
+use crate::{
+    chain::ChainClient,
+    types::{CommitmentTransaction, KeyCommitment},
+};
+
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const RECEIPT_POLL_ATTEMPTS: u32 = 15;
+/// Attempts that find the commitment transaction still not mined before it
+/// is replaced by one paying more gas.
+const REPLACE_AFTER_NOT_MINED: u32 = 3;
+
+sol! {
+    /// Entry point of the key commitment contract.
+    function publishKeyCommitment(uint64 keyId, bytes32 keyHash, address[] participants) external;
+}
+
+/// Submits commitments as `publishKeyCommitment` transactions signed with the
+/// signing key of the node, so the chain node needs no unlocked account.
+///
+/// The signed transaction of a commitment is stored before it is sent and
+/// sent again as is on retries, so a commitment never takes two nonces. A
+/// transaction that stays unmined (e.g. underpriced) is replaced at the same
+/// nonce by one paying more gas. A commitment only counts as published once
+/// one of its transactions is mined and succeeded; a reverted or dropped
+/// transaction is discarded and the next attempt signs a new one.
+pub struct EthereumKeyPublisher {
+    chain_client: ChainClient,
+    contract_address: String,
+    signer: PrivateKeySigner,
+    receipt_poll_interval: Duration,
+    receipt_poll_attempts: u32,
+}
+
+impl EthereumKeyPublisher {
+    pub fn new(
+        chain_rpc_url: String,
+        contract_address: String,
+        signing_key: &str,
+    ) -> Result<Self, PublishError> {
+        let signer = signing_key
+            .trim()
+            .parse()
+            .map_err(|_| PublishError::InvalidSigningKey)?;
+
+        Ok(Self {
+            chain_client: ChainClient::new(chain_rpc_url).map_err(PublishError::Chain)?,
+            contract_address,
+            signer,
+            receipt_poll_interval: RECEIPT_POLL_INTERVAL,
+            receipt_poll_attempts: RECEIPT_POLL_ATTEMPTS,
+        })
+    }
+
+    /// Polls for the receipt `attempts` times, `interval` apart, per attempt
+    /// to publish.
+    pub fn with_receipt_polling(mut self, interval: Duration, attempts: u32) -> Self {
+        self.receipt_poll_interval = interval;
+        self.receipt_poll_attempts = attempts.max(1);
+        self
+    }
+
+    fn account(&self) -> String {
+        self.signer.address().to_string()
+    }
+
+    /// The stored transaction of `key_commitment`, or a newly signed one
+    /// taking the next nonce of the account.
+    async fn commitment_transaction(
+        &self,
+        key_commitment: &KeyCommitment,
+    ) -> Result<CommitmentTransaction, PublishError> {
+        if let Ok(commitment_transaction) = CommitmentTransaction::get(key_commitment.key_id) {
+            return Ok(commitment_transaction);
+        }
+
+        let account = self.account();
+        let calldata = encode_key_commitment(key_commitment)?;
+
+        let nonce = self
+            .chain_client
+            .transaction_count(&account, "pending")
+            .await?;
+        let gas_price = self.chain_client.gas_price().await?;
+        let gas_limit = self
+            .chain_client
+            .estimate_gas(&account, &self.contract_address, &calldata)
+            .await?;
+
+        let commitment_transaction = self
+            .sign(key_commitment, nonce, gas_price, gas_limit)
+            .await?;
+        commitment_transaction.put(key_commitment.key_id)?;
+
+        Ok(commitment_transaction)
+    }
+
+    /// Signs the `publishKeyCommitment` transaction of `key_commitment`.
+    async fn sign(
+        &self,
+        key_commitment: &KeyCommitment,
+        nonce: u64,
+        gas_price: u64,
+        gas_limit: u64,
+    ) -> Result<CommitmentTransaction, PublishError> {
+        let calldata = encode_key_commitment(key_commitment)?;
+        let contract_address: EthereumAddress = self
+            .contract_address
+            .parse()
+            .map_err(|_| PublishError::InvalidCommitment(self.contract_address.clone()))?;
+        let chain_id = self.chain_client.chain_id().await?;
+
+        let mut transaction = TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: gas_price.into(),
+            gas_limit,
+            to: TxKind::Call(contract_address),
+            value: U256::ZERO,
+            input: Bytes::from(calldata),
+        };
+        let signature = self
+            .signer
+            .sign_transaction_sync(&mut transaction)
+            .map_err(|error| PublishError::Sign(error.to_string()))?;
+        let signed_transaction = transaction.into_signed(signature);
+
+        Ok(CommitmentTransaction {
+            nonce,
+            gas_price,
+            gas_limit,
+            hash: signed_transaction.hash().to_string(),
+            raw_transaction: const_hex::encode_prefixed(
+                TxEnvelope::from(signed_transaction).encoded_2718(),
+            ),
+            not_mined_attempts: 0,
+            replaced_hashes: Vec::new(),
+        })
+    }
+
+    /// Counts an attempt that found `commitment_transaction` not mined and,
+    /// after [`REPLACE_AFTER_NOT_MINED`] of them, replaces it by a transaction
+    /// at the same nonce paying more gas, which the next attempt sends.
+    async fn record_not_mined(
+        &self,
+        key_commitment: &KeyCommitment,
+        mut commitment_transaction: CommitmentTransaction,
+    ) -> Result<(), PublishError> {
+        commitment_transaction.not_mined_attempts += 1;
+        if commitment_transaction.not_mined_attempts < REPLACE_AFTER_NOT_MINED {
+            commitment_transaction.put(key_commitment.key_id)?;
+
+            return Ok(());
+        }
+
+        // Chain nodes only accept a replacement paying at least 10% more.
+        let gas_price =
+            (commitment_transaction.gas_price + commitment_transaction.gas_price / 8 + 1)
+                .max(self.chain_client.gas_price().await?);
+        let mut replacement = self
+            .sign(
+                key_commitment,
+                commitment_transaction.nonce,
+                gas_price,
+                commitment_transaction.gas_limit,
+            )
+            .await?;
+
+        // The replaced transaction may still be mined instead.
+        replacement.replaced_hashes = commitment_transaction.replaced_hashes;
+        replacement
+            .replaced_hashes
+            .push(commitment_transaction.hash);
+        replacement.put(key_commitment.key_id)?;
+
+        tracing::info!(
+            "Replaced the commitment transaction - key_id: {:?} / hash: {:?} / gas_price: {}",
+            key_commitment.key_id,
+            replacement.hash,
+            gas_price
+        );
+
+        Ok(())
+    }
+
+    /// Waits a while for the receipt of `commitment_transaction` or of one
+    /// of the transactions it replaced. Returns the hash of the mined one.
+    async fn confirm(
+        &self,
+        commitment_transaction: &CommitmentTransaction,
+    ) -> Result<String, PublishError> {
+        for attempt in 0..self.receipt_poll_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(self.receipt_poll_interval).await;
+            }
+
+            for hash in commitment_transaction.hashes() {
+                if let Some(receipt) = self.chain_client.transaction_receipt(hash).await? {
+                    if !receipt.is_success() {
+                        return Err(PublishError::Reverted(hash.to_owned()));
+                    }
+
+                    return Ok(hash.to_owned());
+                }
+            }
+        }
+
+        // Another transaction took the nonce, so none of these will be mined.
+        let mined_nonce = self
+            .chain_client
+            .transaction_count(&self.account(), "latest")
+            .await?;
+        if mined_nonce > commitment_transaction.nonce {
+            return Err(PublishError::Dropped(commitment_transaction.hash.clone()));
+        }
+
+        Err(PublishError::NotMined(commitment_transaction.hash.clone()))
+    }
+}
+
+impl KeyPublisher for EthereumKeyPublisher {
+    fn publish<'a>(&'a self, key_commitment: &'a KeyCommitment) -> PublishFuture<'a> {
+        Box::pin(async move {
+            let commitment_transaction = self.commitment_transaction(key_commitment).await?;
+
+            // Sending an already known transaction again fails harmlessly; the
+            // receipt tells whether it made it.
+            if let Err(error) = self
+                .chain_client
+                .send_raw_transaction(&commitment_transaction.raw_transaction)
+                .await
+            {
+                tracing::debug!(
+                    "Could not send the commitment transaction - key_id: {:?} / hash: {:?} / error: {}",
+                    key_commitment.key_id,
+                    commitment_transaction.hash,
+                    error
+                );
+            }
+
+            match self.confirm(&commitment_transaction).await {
+                Ok(hash) => {
+                    CommitmentTransaction::delete(key_commitment.key_id)?;
+
+                    Ok(hash)
+                }
+                Err(error @ (PublishError::Reverted(_) | PublishError::Dropped(_))) => {
+                    CommitmentTransaction::delete(key_commitment.key_id)?;
+
+                    Err(error)
+                }
+                Err(error @ PublishError::NotMined(_)) => {
+                    self.record_not_mined(key_commitment, commitment_transaction)
+                        .await?;
+
+                    Err(error)
+                }
+                Err(error) => Err(error),
+            }
+        })
+    }
+
+    fn abandon(&self, key_commitment: &KeyCommitment) -> Result<(), PublishError> {
+        CommitmentTransaction::delete(key_commitment.key_id)?;
+
+        Ok(())
+    }
+}
+
+/// Calldata of `publishKeyCommitment` for `key_commitment`.
+pub fn encode_key_commitment(key_commitment: &KeyCommitment) -> Result<Vec<u8>, PublishError> {
+    let key_hash: B256 = key_commitment
+        .key_hash
+        .parse()
+        .map_err(|_| PublishError::InvalidCommitment(key_commitment.key_hash.clone()))?;
+
+    let participants = key_commitment
+        .participants
+        .iter()
+        .map(|address| {
+            EthereumAddress::try_from(address.as_slice())
+                .map_err(|_| PublishError::InvalidCommitment(address.as_hex_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(publishKeyCommitmentCall {
+        keyId: key_commitment.key_id.as_u64(),
+        keyHash: key_hash,
+        participants,
+    }
+    .abi_encode())
+}
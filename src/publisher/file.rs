@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use super::{KeyPublisher, PublishError, PublishFuture};
+use crate::types::KeyCommitment;
+
+/// Appends commitments to `path`, one JSON object per line.
+pub struct FileKeyPublisher {
+    path: PathBuf,
+}
+
+impl FileKeyPublisher {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl KeyPublisher for FileKeyPublisher {
+    fn publish<'a>(&'a self, key_commitment: &'a KeyCommitment) -> PublishFuture<'a> {
+        Box::pin(async move {
+            let mut line = serde_json::to_vec(key_commitment).map_err(PublishError::Serialize)?;
+            line.push(b'\n');
+
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await
+                .map_err(PublishError::File)?;
+            file.write_all(&line).await.map_err(PublishError::File)?;
+            file.sync_data().await.map_err(PublishError::File)?;
+
+            Ok(self.path.display().to_string())
+        })
+    }
+}
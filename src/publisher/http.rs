@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use super::{KeyPublisher, PublishError, PublishFuture};
+use crate::types::KeyCommitment;
+
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// POSTs commitments as JSON to `url`. Any 2xx status counts as published.
+pub struct HttpKeyPublisher {
+    http_client: reqwest::Client,
+    url: String,
+}
+
+impl HttpKeyPublisher {
+    pub fn new(url: String) -> Result<Self, PublishError> {
+        let http_client = reqwest::Client::builder()
+            .timeout(PUBLISH_TIMEOUT)
+            .build()
+            .map_err(PublishError::Http)?;
+
+        Ok(Self { http_client, url })
+    }
+}
+
+impl KeyPublisher for HttpKeyPublisher {
+    fn publish<'a>(&'a self, key_commitment: &'a KeyCommitment) -> PublishFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .http_client
+                .post(&self.url)
+                .json(key_commitment)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(PublishError::Http)?;
+
+            Ok(response.status().to_string())
+        })
+    }
+}
@@ -0,0 +1,94 @@
+mod ethereum;
+mod file;
+mod http;
+
+use std::{fs, future::Future, pin::Pin, sync::Arc};
+
+pub use ethereum::*;
+pub use file::*;
+pub use http::*;
+
+use crate::{
+    chain::ChainError,
+    types::{Config, KeyCommitment, KeyPublisherKind, SIGNING_KEY},
+};
+
+/// Resolves to a sink specific receipt, e.g. a transaction hash.
+pub type PublishFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<String, PublishError>> + Send + 'a>>;
+
+/// Makes a [`KeyCommitment`] available to consumers outside the cluster.
+///
+/// A commitment may be handed over more than once when an earlier attempt
+/// failed halfway, so sinks should tolerate duplicates.
+pub trait KeyPublisher: Send + Sync {
+    fn publish<'a>(&'a self, key_commitment: &'a KeyCommitment) -> PublishFuture<'a>;
+
+    /// Called once publishing `key_commitment` is given up, to drop whatever
+    /// the sink keeps for later attempts.
+    fn abandon(&self, _key_commitment: &KeyCommitment) -> Result<(), PublishError> {
+        Ok(())
+    }
+}
+
+/// Builds the publisher selected by `key_publisher`, or `None` if
+/// commitments are not published.
+pub fn key_publisher(config: &Config) -> Result<Option<Arc<dyn KeyPublisher>>, PublishError> {
+    let key_publisher: Arc<dyn KeyPublisher> = match config.key_publisher() {
+        KeyPublisherKind::None => return Ok(None),
+        KeyPublisherKind::Ethereum => Arc::new(EthereumKeyPublisher::new(
+            config.chain_rpc_url().clone().unwrap_or_default(),
+            config
+                .key_publisher_contract_address()
+                .clone()
+                .unwrap_or_default(),
+            &fs::read_to_string(config.path().join(SIGNING_KEY)).map_err(PublishError::File)?,
+        )?),
+        KeyPublisherKind::Http => Arc::new(HttpKeyPublisher::new(
+            config.key_publisher_url().clone().unwrap_or_default(),
+        )?),
+        KeyPublisherKind::File => Arc::new(FileKeyPublisher::new(
+            config.key_publisher_file_path().clone().unwrap_or_default(),
+        )),
+    };
+
+    Ok(Some(key_publisher))
+}
+
+#[derive(Debug)]
+pub enum PublishError {
+    Chain(ChainError),
+    Http(reqwest::Error),
+    File(std::io::Error),
+    Serialize(serde_json::Error),
+    Database(radius_sdk::kvstore::KvStoreError),
+    InvalidCommitment(String),
+    InvalidSigningKey,
+    Sign(String),
+    /// The commitment transaction was mined but reverted.
+    Reverted(String),
+    /// Another transaction took the nonce of the commitment transaction.
+    Dropped(String),
+    /// The commitment transaction is not mined yet.
+    NotMined(String),
+}
+
+impl std::fmt::Display for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for PublishError {}
+
+impl From<ChainError> for PublishError {
+    fn from(value: ChainError) -> Self {
+        Self::Chain(value)
+    }
+}
+
+impl From<radius_sdk::kvstore::KvStoreError> for PublishError {
+    fn from(value: radius_sdk::kvstore::KvStoreError) -> Self {
+        Self::Database(value)
+    }
+}
@@ -0,0 +1,58 @@
+use alloy_sol_types::{sol, SolCall};
+use radius_sdk::signature::Address;
+
+use crate::{
+    chain::{ChainClient, ChainError},
+    types::KeyGenerator,
+};
+
+sol! {
+    /// Entry of the on-chain key generator registry.
+    struct RegisteredKeyGenerator {
+        address keyGenerator;
+        string clusterRpcUrl;
+        string externalRpcUrl;
+    }
+
+    /// View exposed by the key generator registry contract.
+    function getKeyGenerators() external view returns (RegisteredKeyGenerator[] memory);
+}
+
+impl From<RegisteredKeyGenerator> for KeyGenerator {
+    fn from(registered: RegisteredKeyGenerator) -> Self {
+        KeyGenerator::new(
+            Address::from(registered.keyGenerator.to_vec()),
+            registered.clusterRpcUrl,
+            registered.externalRpcUrl,
+        )
+    }
+}
+
+/// Reads the key generator set from the registry contract at
+/// `contract_address` as of `block_number`.
+pub async fn fetch_registered_key_generators(
+    chain_client: &ChainClient,
+    contract_address: &str,
+    block_number: Option<u64>,
+) -> Result<Vec<KeyGenerator>, ChainError> {
+    let return_data = chain_client
+        .call(
+            contract_address,
+            &getKeyGeneratorsCall {}.abi_encode(),
+            block_number,
+        )
+        .await?;
+
+    decode_registered_key_generators(&return_data)
+}
+
+/// Decodes the return data of `getKeyGenerators()`.
+pub fn decode_registered_key_generators(
+    return_data: &[u8],
+) -> Result<Vec<KeyGenerator>, ChainError> {
+    let registered = getKeyGeneratorsCall::abi_decode_returns(return_data, true)
+        .map_err(|error| ChainError::InvalidResponse(error.to_string()))?
+        ._0;
+
+    Ok(registered.into_iter().map(KeyGenerator::from).collect())
+}
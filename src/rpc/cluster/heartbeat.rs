@@ -0,0 +1,30 @@
+use radius_sdk::signature::Address;
+
+use crate::rpc::prelude::*;
+
+/// Liveness probe sent periodically to every other key generator.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Heartbeat {
+    pub address: Address,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HeartbeatResponse {
+    pub address: Address,
+    pub key_id: KeyId,
+}
+
+impl RpcParameter<AppState> for Heartbeat {
+    type Response = HeartbeatResponse;
+
+    fn method() -> &'static str {
+        "heartbeat"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        Ok(HeartbeatResponse {
+            address: context.config().address().clone(),
+            key_id: KeyId::get()?,
+        })
+    }
+}
@@ -1,11 +1,15 @@
 mod get_key_generator_list;
+mod heartbeat;
 mod run_generate_partial_key;
 mod sync_aggregated_key;
+mod sync_aggregated_key_signature;
 mod sync_key_generator;
 mod sync_partial_key;
 
 pub use get_key_generator_list::*;
+pub use heartbeat::*;
 pub use run_generate_partial_key::*;
 pub use sync_aggregated_key::*;
+pub use sync_aggregated_key_signature::*;
 pub use sync_key_generator::*;
 pub use sync_partial_key::*;
@@ -1,16 +1,13 @@
 use radius_sdk::{
-    json_rpc::{
-        client::{Id, RpcClient},
-        server::{RpcError, RpcParameter},
-    },
+    json_rpc::server::{RpcError, RpcParameter},
     signature::Address,
 };
 use serde::{Deserialize, Serialize};
 use skde::key_generation::{
-    generate_partial_key, prove_partial_key_validity, PartialKey, PartialKeyProof,
+    generate_partial_key, prove_partial_key_validity, PartialKey as SkdePartialKey, PartialKeyProof,
 };
 
-use crate::rpc::{cluster::SyncPartialKey, prelude::*};
+use crate::rpc::{cluster::SyncPartialKey, prelude::*, spawn_multicast};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RunGeneratePartialKey {
@@ -24,51 +21,60 @@ impl RpcParameter<AppState> for RunGeneratePartialKey {
         "run_generate_partial_key"
     }
 
+    #[tracing::instrument(name = "round", skip_all, fields(key_id = self.key_id.as_u64()))]
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let my_address = context.config().signer().address().clone();
+
+        // Keep the list locked until the own partial key is stored, so that a
+        // repeated request for the same round never generates a second one.
+        PartialKeyAddressList::initialize(self.key_id)?;
+        let mut partial_key_address_list = PartialKeyAddressList::get_mut(self.key_id)?;
+        if partial_key_address_list.contains(&my_address) {
+            return Ok(());
+        }
+
         let skde_params = context.skde_params();
 
         let (secret_value, partial_key) = generate_partial_key(skde_params);
 
         let partial_key_proof = prove_partial_key_validity(skde_params, &secret_value);
 
+        PartialKey::new(partial_key.clone()).put(self.key_id, &my_address)?;
+        partial_key_address_list.insert(my_address.clone());
+        partial_key_address_list.update()?;
+
         sync_partial_key(
-            context.config().signer().address().clone(),
+            &context,
+            my_address,
             self.key_id,
             partial_key,
             partial_key_proof,
-        );
+        )?;
 
         Ok(())
     }
 }
 
 pub fn sync_partial_key(
+    context: &AppState,
     address: Address,
     key_id: KeyId,
-    partial_key: PartialKey,
+    partial_key: SkdePartialKey,
     partial_key_proof: PartialKeyProof,
-) {
-    let all_key_generator_rpc_url_list = KeyGeneratorList::get()
-        .unwrap()
-        .get_all_key_generator_rpc_url_list();
+) -> Result<(), Error> {
+    let other_key_generator_rpc_url_list = KeyGeneratorList::get()?
+        .get_other_alive_key_generator_rpc_url_list(context.config().address());
 
-    tokio::spawn(async move {
-        let parameter = SyncPartialKey {
+    spawn_multicast(
+        context,
+        other_key_generator_rpc_url_list,
+        SyncPartialKey {
             address,
             key_id,
             skde_partial_key: partial_key,
             partial_key_proof,
-        };
+        },
+    );
 
-        let rpc_client = RpcClient::new().unwrap();
-        rpc_client
-            .multicast(
-                all_key_generator_rpc_url_list,
-                SyncPartialKey::method(),
-                &parameter,
-                Id::Null,
-            )
-            .await
-            .unwrap();
-    });
+    Ok(())
 }
@@ -1,20 +1,27 @@
+use std::time::Instant;
+
 use radius_sdk::{
     json_rpc::server::{RpcError, RpcParameter},
     signature::Address,
 };
 use serde::{Deserialize, Serialize};
-use skde::{
-    delay_encryption::solve_time_lock_puzzle,
-    key_aggregation::{aggregate_key, AggregatedKey as SkdeAggregatedKey},
-};
+use skde::key_aggregation::AggregatedKey as SkdeAggregatedKey;
+use tracing::Instrument;
 
-use crate::rpc::prelude::*;
+use crate::{
+    database,
+    rpc::prelude::*,
+    task::round::{aggregate_participant_partial_keys, solve_decryption_key},
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncAggregatedKey {
     pub key_id: KeyId,
     pub aggregated_key: SkdeAggregatedKey,
     pub participant_addresses: Vec<Address>,
+    /// Set when the leader follows the block height.
+    #[serde(default)]
+    pub block_range: Option<BlockRange>,
 }
 
 impl RpcParameter<AppState> for SyncAggregatedKey {
@@ -24,37 +31,49 @@ impl RpcParameter<AppState> for SyncAggregatedKey {
         "sync_aggregated_key"
     }
 
+    #[tracing::instrument(name = "round", skip_all, fields(key_id = self.key_id.as_u64()))]
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
-        let skde_params = context.skde_params().clone();
+        // A repeated delivery must not aggregate again or start a second
+        // puzzle job for the same key.
+        if AggregatedKey::get(self.key_id).is_ok() {
+            return Ok(());
+        }
 
-        let partial_key_address_list =
-            PartialKeyAddressList::get_or(self.key_id, PartialKeyAddressList::default)?;
+        if let Some(block_range) = self.block_range {
+            block_range.put(self.key_id)?;
+        }
 
-        let partial_key_list = partial_key_address_list.get_partial_key_list(self.key_id)?;
+        // Aggregating the partial keys this node received could give another
+        // key than the leader's when the sets differ, e.g. after a partition.
+        let skde_aggregated_key = aggregate_participant_partial_keys(
+            &context,
+            self.key_id,
+            &self.participant_addresses,
+            &self.aggregated_key,
+        )?;
+        let aggregated_at = Instant::now();
 
-        let skde_aggregated_key = aggregate_key(&skde_params, &partial_key_list);
-        let aggregated_key = AggregatedKey::new(skde_aggregated_key.clone());
-        aggregated_key.put(self.key_id)?;
+        database::spawn(
+            async move {
+                if let Err(error) =
+                    solve_decryption_key(&context, self.key_id, skde_aggregated_key, aggregated_at)
+                        .await
+                {
+                    tracing::error!(
+                        "Failed to solve decryption key - key_id: {:?} / error: {}",
+                        self.key_id,
+                        error
+                    );
 
-        tracing::info!(
-            "Completed to generate encryption key - key id: {:?} / encryption key: {:?}",
-            self.key_id,
-            skde_aggregated_key.u
+                    context.publish_key_event(KeyEvent::RoundFailed {
+                        key_id: self.key_id,
+                        reason: error.to_string(),
+                    });
+                }
+            }
+            .in_current_span(),
         );
 
-        tokio::spawn(async move {
-            let decryption_key =
-                solve_time_lock_puzzle(&skde_params, &skde_aggregated_key).unwrap();
-            let decryption_key = DecryptionKey::new(decryption_key.sk.clone());
-
-            decryption_key.put(self.key_id).unwrap();
-            tracing::info!(
-                "Complete to get decryption key - key_id: {:?} / decryption key: {:?}",
-                self.key_id,
-                decryption_key
-            );
-        });
-
         Ok(())
     }
 }
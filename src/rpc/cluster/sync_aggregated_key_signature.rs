@@ -0,0 +1,128 @@
+use radius_sdk::json_rpc::server::{RpcError, RpcParameter};
+use serde::{Deserialize, Serialize};
+
+use crate::rpc::{prelude::*, spawn_multicast};
+
+/// Carries a key generator's signature over the aggregated key of `key_id`.
+/// The receiver rebuilds the signed message from its own aggregated key, so
+/// only signatures over the same key end up in its certificate.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncAggregatedKeySignature {
+    pub key_id: KeyId,
+    pub key_signature: KeySignature,
+}
+
+impl RpcParameter<AppState> for SyncAggregatedKeySignature {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "sync_aggregated_key_signature"
+    }
+
+    #[tracing::instrument(
+        name = "round",
+        skip_all,
+        fields(key_id = self.key_id.as_u64(), peer = %self.key_signature.signer.as_hex_string())
+    )]
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        if !KeyGeneratorList::get()?.is_key_generator_in_cluster(&self.key_signature.signer) {
+            return Ok(());
+        }
+
+        let Ok(aggregated_key) = AggregatedKey::get(self.key_id) else {
+            // The signer aggregated first. Keep the signature until this node
+            // has a key to check it against.
+            PendingKeySignatures::push(self.key_id, self.key_signature)?;
+
+            // The key may have been aggregated in the meantime, after the
+            // pending signatures were last checked.
+            if let Ok(aggregated_key) = AggregatedKey::get(self.key_id) {
+                let message = KeySignatureMessage::new(
+                    self.key_id,
+                    aggregated_key.encryption_key(),
+                    context.skde_params_hash().to_owned(),
+                );
+                add_pending_key_signatures(&context, &message)?;
+            }
+
+            return Ok(());
+        };
+
+        let message = KeySignatureMessage::new(
+            self.key_id,
+            aggregated_key.encryption_key(),
+            context.skde_params_hash().to_owned(),
+        );
+
+        self.key_signature
+            .verify(*context.config().chain_type(), &message)?;
+
+        tracing::info!(
+            "Sync aggregated key signature - key_id: {:?}, signer: {:?}",
+            self.key_id,
+            self.key_signature.signer.as_hex_string(),
+        );
+
+        EncryptionKeyCertificate::add_signature(&message, self.key_signature)?;
+
+        Ok(())
+    }
+}
+
+/// Adds the signatures that arrived before the key of `message` was
+/// aggregated, dropping those over another key.
+fn add_pending_key_signatures(
+    context: &AppState,
+    message: &KeySignatureMessage,
+) -> Result<(), Error> {
+    for key_signature in PendingKeySignatures::take(message.key_id)? {
+        if key_signature
+            .verify(*context.config().chain_type(), message)
+            .is_err()
+        {
+            tracing::warn!(
+                "Dropped pending aggregated key signature - key_id: {:?}, signer: {:?}",
+                message.key_id,
+                key_signature.signer.as_hex_string(),
+            );
+            continue;
+        }
+
+        EncryptionKeyCertificate::add_signature(message, key_signature)?;
+    }
+
+    Ok(())
+}
+
+/// Signs the locally stored aggregated key of `key_id`, records the signature
+/// (and those that arrived before the key was aggregated) in the local
+/// certificate and sends it to every other key generator.
+pub fn sync_aggregated_key_signature(
+    context: &AppState,
+    key_id: KeyId,
+    encryption_key: String,
+) -> Result<(), Error> {
+    let message = KeySignatureMessage::new(
+        key_id,
+        encryption_key,
+        context.skde_params_hash().to_owned(),
+    );
+    let key_signature = KeySignature::sign(context.config().signer(), &message)?;
+
+    EncryptionKeyCertificate::add_signature(&message, key_signature.clone())?;
+    add_pending_key_signatures(context, &message)?;
+
+    let other_key_generator_rpc_url_list = KeyGeneratorList::get()?
+        .get_other_alive_key_generator_rpc_url_list(context.config().address());
+
+    spawn_multicast(
+        context,
+        other_key_generator_rpc_url_list,
+        SyncAggregatedKeySignature {
+            key_id,
+            key_signature,
+        },
+    );
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::{
+    error::Error,
     state::AppState,
     types::{KeyGenerator, KeyGeneratorList},
 };
@@ -23,6 +24,18 @@ struct SyncKeyGeneratorMessage {
     external_rpc_url: String,
 }
 
+impl SyncKeyGenerator {
+    pub fn new(address: Address, cluster_rpc_url: String, external_rpc_url: String) -> Self {
+        Self {
+            message: SyncKeyGeneratorMessage {
+                address,
+                cluster_rpc_url,
+                external_rpc_url,
+            },
+        }
+    }
+}
+
 impl RpcParameter<AppState> for SyncKeyGenerator {
     type Response = ();
 
@@ -30,7 +43,12 @@ impl RpcParameter<AppState> for SyncKeyGenerator {
         "sync_key_generator"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        // The registry contract is the only source of membership when set.
+        if context.config().registry_contract_address().is_some() {
+            return Err(Error::MembershipManagedByRegistry.into());
+        }
+
         info!(
             "Sync key generator - address: {:?} / cluster_rpc_url: {:?} / external_rpc_url: {:?}",
             self.message.address.as_hex_string(),
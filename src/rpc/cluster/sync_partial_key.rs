@@ -7,7 +7,7 @@ use skde::key_generation::{
     verify_partial_key_validity, PartialKey as SkdePartialKey, PartialKeyProof,
 };
 
-use crate::rpc::prelude::*;
+use crate::{metrics, rpc::prelude::*};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SyncPartialKey {
@@ -24,34 +24,53 @@ impl RpcParameter<AppState> for SyncPartialKey {
         "sync_partial_key"
     }
 
+    #[tracing::instrument(
+        name = "round",
+        skip_all,
+        fields(key_id = self.key_id.as_u64(), peer = %self.address.as_hex_string())
+    )]
     async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
-        if KeyGeneratorList::get()?.is_key_generator_in_cluster(&self.address) {
-            tracing::info!(
-                "Sync partial key - key_id: {:?}, address: {:?}",
-                self.key_id,
-                self.address.as_hex_string(),
-            );
-
-            PartialKeyAddressList::initialize(self.key_id)?;
-
-            let is_valid = verify_partial_key_validity(
-                context.skde_params(),
-                self.skde_partial_key.clone(),
-                self.partial_key_proof,
-            );
-
-            if !is_valid {
-                return Ok(());
-            }
-
-            PartialKeyAddressList::apply(self.key_id, |list| {
-                list.insert(self.address.clone());
-            })?;
-
-            let partial_key = PartialKey::new(self.skde_partial_key.clone());
-            partial_key.put(self.key_id, &self.address)?;
+        let peer = self.address.as_hex_string();
+
+        if !KeyGeneratorList::get()?.is_key_generator_in_cluster(&self.address) {
+            metrics::record_partial_key_rejected(metrics::UNKNOWN_PEER, "not_member");
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Sync partial key - key_id: {:?}, address: {:?}",
+            self.key_id,
+            peer,
+        );
+
+        PartialKeyAddressList::initialize(self.key_id)?;
+
+        let is_valid = verify_partial_key_validity(
+            context.skde_params(),
+            self.skde_partial_key.clone(),
+            self.partial_key_proof,
+        );
+
+        if !is_valid {
+            metrics::record_partial_key_rejected(&peer, "invalid_proof");
+            return Ok(());
         }
 
+        // The first partial key of an address wins, so a repeated delivery
+        // is ignored and every node aggregates the same keys.
+        let mut partial_key_address_list = PartialKeyAddressList::get_mut(self.key_id)?;
+        if partial_key_address_list.contains(&self.address) {
+            return Ok(());
+        }
+
+        let partial_key = PartialKey::new(self.skde_partial_key.clone());
+        partial_key.put(self.key_id, &self.address)?;
+
+        partial_key_address_list.insert(self.address.clone());
+        partial_key_address_list.update()?;
+
+        metrics::record_partial_key_received(&peer);
+
         Ok(())
     }
 }
@@ -0,0 +1,27 @@
+use crate::{encryption::decrypt, rpc::prelude::*};
+
+/// Only registered when `enable_encryption_rpc` is set.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Decrypt {
+    pub key_id: KeyId,
+    pub ciphertext: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DecryptResponse {
+    pub plaintext: String,
+}
+
+impl RpcParameter<AppState> for Decrypt {
+    type Response = DecryptResponse;
+
+    fn method() -> &'static str {
+        "decrypt"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let plaintext = decrypt(context.skde_params(), self.key_id, &self.ciphertext)?;
+
+        Ok(DecryptResponse { plaintext })
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{encryption::encrypt, rpc::prelude::*};
+
+/// Only registered when `enable_encryption_rpc` is set.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Encrypt {
+    pub key_id: KeyId,
+    pub plaintext: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncryptResponse {
+    pub ciphertext: String,
+}
+
+impl RpcParameter<AppState> for Encrypt {
+    type Response = EncryptResponse;
+
+    fn method() -> &'static str {
+        "encrypt"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let ciphertext = encrypt(context.skde_params(), self.key_id, &self.plaintext)?;
+
+        Ok(EncryptResponse { ciphertext })
+    }
+}
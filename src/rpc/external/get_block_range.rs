@@ -0,0 +1,25 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockRange {
+    pub key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetBlockRangeResponse {
+    pub block_range: BlockRange,
+}
+
+impl RpcParameter<AppState> for GetBlockRange {
+    type Response = GetBlockRangeResponse;
+
+    fn method() -> &'static str {
+        "get_block_range"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let block_range = BlockRange::get(self.key_id)?;
+
+        Ok(GetBlockRangeResponse { block_range })
+    }
+}
@@ -3,12 +3,13 @@ use crate::rpc::prelude::*;
 /// 09/05
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetDecryptionKey {
-    key_id: KeyId,
+    pub key_id: KeyId,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetDecryptionKeyResponse {
     pub decryption_key: String,
+    pub key_signature: KeySignature,
 }
 
 impl RpcParameter<AppState> for GetDecryptionKey {
@@ -18,11 +19,32 @@ impl RpcParameter<AppState> for GetDecryptionKey {
         "get_decryption_key"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
-        let decryption_key = DecryptionKey::get(self.key_id)?;
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let decryption_key = DecryptionKey::get(self.key_id)?.as_string();
+        let key_signature = context.sign_key(self.key_id, decryption_key.clone())?;
 
         Ok(GetDecryptionKeyResponse {
-            decryption_key: decryption_key.as_string(),
+            decryption_key,
+            key_signature,
         })
     }
 }
+
+impl GetDecryptionKeyResponse {
+    /// Checks that the response was signed by `key_signature.signer` for
+    /// `key_id` under the SKDE parameters hashed to `skde_params_hash`.
+    pub fn verify(
+        &self,
+        chain_type: ChainType,
+        key_id: KeyId,
+        skde_params_hash: &str,
+    ) -> Result<(), SignatureError> {
+        let message = KeySignatureMessage::new(
+            key_id,
+            self.decryption_key.clone(),
+            skde_params_hash.to_owned(),
+        );
+
+        self.key_signature.verify(chain_type, &message)
+    }
+}
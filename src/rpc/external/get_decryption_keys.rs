@@ -0,0 +1,40 @@
+use crate::rpc::{external::key_id_range, prelude::*};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetDecryptionKeys {
+    pub from_key_id: KeyId,
+    pub to_key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DecryptionKeyEntry {
+    pub key_id: KeyId,
+    /// `None` while the time-lock puzzle for `key_id` has not been solved yet.
+    pub decryption_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetDecryptionKeysResponse {
+    pub decryption_keys: Vec<DecryptionKeyEntry>,
+}
+
+impl RpcParameter<AppState> for GetDecryptionKeys {
+    type Response = GetDecryptionKeysResponse;
+
+    fn method() -> &'static str {
+        "get_decryption_keys"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let decryption_keys = key_id_range(self.from_key_id, self.to_key_id)?
+            .map(|key_id| DecryptionKeyEntry {
+                key_id,
+                decryption_key: DecryptionKey::get(key_id)
+                    .ok()
+                    .map(|decryption_key| decryption_key.as_string()),
+            })
+            .collect();
+
+        Ok(GetDecryptionKeysResponse { decryption_keys })
+    }
+}
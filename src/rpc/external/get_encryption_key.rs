@@ -3,12 +3,13 @@ use crate::rpc::prelude::*;
 /// 09/05
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetEncryptionKey {
-    key_id: KeyId,
+    pub key_id: KeyId,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetEncryptionKeyResponse {
     pub encryption_key: String,
+    pub key_signature: KeySignature,
 }
 
 impl RpcParameter<AppState> for GetEncryptionKey {
@@ -18,12 +19,33 @@ impl RpcParameter<AppState> for GetEncryptionKey {
         "get_encryption_key"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
         let aggregated_key = AggregatedKey::get(self.key_id)?;
         let encryption_key = aggregated_key.encryption_key();
+        let key_signature = context.sign_key(self.key_id, encryption_key.clone())?;
 
         Ok(GetEncryptionKeyResponse {
-            encryption_key: encryption_key,
+            encryption_key,
+            key_signature,
         })
     }
 }
+
+impl GetEncryptionKeyResponse {
+    /// Checks that the response was signed by `key_signature.signer` for
+    /// `key_id` under the SKDE parameters hashed to `skde_params_hash`.
+    pub fn verify(
+        &self,
+        chain_type: ChainType,
+        key_id: KeyId,
+        skde_params_hash: &str,
+    ) -> Result<(), SignatureError> {
+        let message = KeySignatureMessage::new(
+            key_id,
+            self.encryption_key.clone(),
+            skde_params_hash.to_owned(),
+        );
+
+        self.key_signature.verify(chain_type, &message)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEncryptionKeyCertificate {
+    pub key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEncryptionKeyCertificateResponse {
+    pub certificate: EncryptionKeyCertificate,
+}
+
+impl RpcParameter<AppState> for GetEncryptionKeyCertificate {
+    type Response = GetEncryptionKeyCertificateResponse;
+
+    fn method() -> &'static str {
+        "get_encryption_key_certificate"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let certificate = EncryptionKeyCertificate::get(self.key_id)?;
+
+        Ok(GetEncryptionKeyCertificateResponse { certificate })
+    }
+}
@@ -0,0 +1,58 @@
+use crate::rpc::prelude::*;
+
+/// Upper bound on the number of key ids a single batch request may cover.
+pub const MAX_KEY_ID_RANGE: u64 = 256;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEncryptionKeys {
+    pub from_key_id: KeyId,
+    pub to_key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncryptionKeyEntry {
+    pub key_id: KeyId,
+    /// `None` while the key for `key_id` has not been aggregated yet.
+    pub encryption_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetEncryptionKeysResponse {
+    pub encryption_keys: Vec<EncryptionKeyEntry>,
+}
+
+impl RpcParameter<AppState> for GetEncryptionKeys {
+    type Response = GetEncryptionKeysResponse;
+
+    fn method() -> &'static str {
+        "get_encryption_keys"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let encryption_keys = key_id_range(self.from_key_id, self.to_key_id)?
+            .map(|key_id| EncryptionKeyEntry {
+                key_id,
+                encryption_key: AggregatedKey::get(key_id)
+                    .ok()
+                    .map(|aggregated_key| aggregated_key.encryption_key()),
+            })
+            .collect();
+
+        Ok(GetEncryptionKeysResponse { encryption_keys })
+    }
+}
+
+/// Validates an inclusive key id range against [`MAX_KEY_ID_RANGE`].
+pub fn key_id_range(
+    from_key_id: KeyId,
+    to_key_id: KeyId,
+) -> Result<impl Iterator<Item = KeyId>, Error> {
+    let from = from_key_id.as_u64();
+    let to = to_key_id.as_u64();
+
+    if from > to || to - from >= MAX_KEY_ID_RANGE {
+        return Err(Error::InvalidKeyIdRange);
+    }
+
+    Ok((from..=to).map(KeyId::new))
+}
@@ -0,0 +1,29 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetKeySlotClock {}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetKeySlotClockResponse {
+    /// `None` if the cluster uses the leader-owned key id counter.
+    pub key_slot_clock: Option<KeySlotClock>,
+    pub current_key_id: Option<KeyId>,
+}
+
+impl RpcParameter<AppState> for GetKeySlotClock {
+    type Response = GetKeySlotClockResponse;
+
+    fn method() -> &'static str {
+        "get_key_slot_clock"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let key_slot_clock = context.config().key_slot_clock();
+
+        Ok(GetKeySlotClockResponse {
+            key_slot_clock,
+            current_key_id: key_slot_clock
+                .and_then(|key_slot_clock| key_slot_clock.current_key_id()),
+        })
+    }
+}
@@ -7,6 +7,7 @@ pub struct GetLatestEncryptionKey {}
 pub struct GetLatestEncryptionKeyResponse {
     pub key_id: KeyId,
     pub encryption_key: String,
+    pub key_signature: KeySignature,
 }
 
 impl RpcParameter<AppState> for GetLatestEncryptionKey {
@@ -16,22 +17,37 @@ impl RpcParameter<AppState> for GetLatestEncryptionKey {
         "get_latest_encryption_key"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
-        let mut key_id = KeyId::get()?;
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let key_id = LatestAggregatedKeyId::get()
+            .map_err(|_| Error::EncryptionKeyNotReady)?
+            .key_id();
 
-        loop {
-            if AggregatedKey::get(key_id).is_err() {
-                key_id.decrease_key_id();
-                continue;
-            }
+        let aggregated_key = AggregatedKey::get(key_id)?;
+        let encryption_key = aggregated_key.encryption_key();
+        let key_signature = context.sign_key(key_id, encryption_key.clone())?;
 
-            let aggregated_key = AggregatedKey::get(key_id)?;
-            let encryption_key = aggregated_key.encryption_key();
+        Ok(GetLatestEncryptionKeyResponse {
+            key_id,
+            encryption_key,
+            key_signature,
+        })
+    }
+}
 
-            return Ok(GetLatestEncryptionKeyResponse {
-                key_id,
-                encryption_key,
-            });
-        }
+impl GetLatestEncryptionKeyResponse {
+    /// Checks that the response was signed by `key_signature.signer` under the
+    /// SKDE parameters hashed to `skde_params_hash`.
+    pub fn verify(
+        &self,
+        chain_type: ChainType,
+        skde_params_hash: &str,
+    ) -> Result<(), SignatureError> {
+        let message = KeySignatureMessage::new(
+            self.key_id,
+            self.encryption_key.clone(),
+            skde_params_hash.to_owned(),
+        );
+
+        self.key_signature.verify(chain_type, &message)
     }
 }
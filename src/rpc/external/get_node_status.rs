@@ -0,0 +1,116 @@
+use crate::{rpc::prelude::*, state::PeerDelivery};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetNodeStatus {}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeRole {
+    Leader,
+    Follower,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStatus {
+    pub address: String,
+    pub cluster_rpc_url: String,
+    /// Whether the peer answered the latest heartbeat.
+    pub reachable: bool,
+    /// Round trip time of the latest heartbeat, if the peer answered.
+    pub rtt_ms: Option<u64>,
+    /// Outcome of the latest multicast sent to the peer.
+    pub last_delivery: Option<PeerDelivery>,
+    /// Heartbeat history, if the peer has been probed yet.
+    pub liveness: Option<PeerLiveness>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetNodeStatusResponse {
+    pub role: NodeRole,
+    pub address: String,
+    pub key_id: KeyId,
+    pub latest_aggregated_key_id: Option<KeyId>,
+    pub latest_decryption_key_id: Option<KeyId>,
+    /// Number of time-lock puzzles currently being solved.
+    pub pending_puzzle_count: usize,
+    /// Rounds driven by this node since start, only counted on the leader.
+    pub rounds_succeeded: u64,
+    pub rounds_failed: u64,
+    pub peer_count: usize,
+    pub reachable_peer_count: usize,
+    pub peers: Vec<PeerStatus>,
+    pub version: String,
+    pub uptime_secs: u64,
+}
+
+impl RpcParameter<AppState> for GetNodeStatus {
+    type Response = GetNodeStatusResponse;
+
+    fn method() -> &'static str {
+        "get_node_status"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let role = match context.config().seed_cluster_rpc_url() {
+            Some(_) => NodeRole::Follower,
+            None => NodeRole::Leader,
+        };
+
+        let peers = peer_statuses(&context)?;
+        let reachable_peer_count = peers.iter().filter(|peer| peer.reachable).count();
+        let round_stats = context.round_stats();
+
+        Ok(GetNodeStatusResponse {
+            role,
+            address: context.config().address().as_hex_string(),
+            key_id: KeyId::get()?,
+            latest_aggregated_key_id: LatestAggregatedKeyId::get()
+                .ok()
+                .map(|latest| latest.key_id()),
+            latest_decryption_key_id: LatestDecryptionKeyId::get()
+                .ok()
+                .map(|latest| latest.key_id()),
+            pending_puzzle_count: context.pending_puzzle_count(),
+            rounds_succeeded: round_stats.succeeded,
+            rounds_failed: round_stats.failed,
+            peer_count: peers.len(),
+            reachable_peer_count,
+            peers,
+            version: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            uptime_secs: context.uptime_secs(),
+        })
+    }
+}
+
+/// Reports every other key generator as last seen by the heartbeat task, so
+/// the status never sends requests of its own.
+fn peer_statuses(context: &AppState) -> Result<Vec<PeerStatus>, Error> {
+    let my_address = context.config().address();
+
+    let mut peers: Vec<PeerStatus> = KeyGeneratorList::get()?
+        .iter()
+        .filter(|key_generator| key_generator.address() != my_address)
+        .map(|key_generator| {
+            let cluster_rpc_url = key_generator.cluster_rpc_url().to_owned();
+            let liveness = PeerLiveness::get(key_generator.address()).ok();
+            let reachable = liveness.as_ref().is_some_and(|liveness| {
+                liveness.last_seen_at.is_some() && liveness.consecutive_failures == 0
+            });
+
+            PeerStatus {
+                address: key_generator.address().as_hex_string(),
+                last_delivery: context.peer_delivery(&cluster_rpc_url),
+                cluster_rpc_url,
+                reachable,
+                rtt_ms: liveness
+                    .as_ref()
+                    .and_then(|liveness| liveness.rtt_ms)
+                    .filter(|_| reachable),
+                liveness,
+            }
+        })
+        .collect();
+    peers.sort_by(|a, b| a.address.cmp(&b.address));
+
+    Ok(peers)
+}
@@ -0,0 +1,25 @@
+use crate::rpc::prelude::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetPublishStatus {
+    pub key_id: KeyId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetPublishStatusResponse {
+    pub publish_status: PublishStatus,
+}
+
+impl RpcParameter<AppState> for GetPublishStatus {
+    type Response = GetPublishStatusResponse;
+
+    fn method() -> &'static str {
+        "get_publish_status"
+    }
+
+    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+        let publish_status = PublishStatus::get(self.key_id)?;
+
+        Ok(GetPublishStatusResponse { publish_status })
+    }
+}
@@ -5,7 +5,9 @@ pub struct GetSkdeParams {}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GetSkdeParamsResponse {
-    skde_params: skde::delay_encryption::SkdeParams,
+    pub skde_params: skde::delay_encryption::SkdeParams,
+    /// Hash that key signatures are bound to; see [`skde_params_hash`].
+    pub skde_params_hash: String,
 }
 
 impl RpcParameter<AppState> for GetSkdeParams {
@@ -20,6 +22,7 @@ impl RpcParameter<AppState> for GetSkdeParams {
 
         Ok(GetSkdeParamsResponse {
             skde_params: skde_params.clone(),
+            skde_params_hash: context.skde_params_hash().to_owned(),
         })
     }
 }
@@ -1,11 +1,33 @@
+mod decrypt;
+mod encrypt;
+mod get_block_range;
 mod get_decryption_key;
+mod get_decryption_keys;
 mod get_encryption_key;
+mod get_encryption_key_certificate;
+mod get_encryption_keys;
+mod get_key_slot_clock;
 mod get_latest_encryption_key;
 mod get_latest_key_id;
+mod get_node_status;
+mod get_publish_status;
 mod get_skde_params;
+mod poll_decryption_keys;
+mod poll_encryption_keys;
 
+pub use decrypt::*;
+pub use encrypt::*;
+pub use get_block_range::*;
 pub use get_decryption_key::*;
+pub use get_decryption_keys::*;
 pub use get_encryption_key::*;
+pub use get_encryption_key_certificate::*;
+pub use get_encryption_keys::*;
+pub use get_key_slot_clock::*;
 pub use get_latest_encryption_key::*;
 pub use get_latest_key_id::*;
+pub use get_node_status::*;
+pub use get_publish_status::*;
 pub use get_skde_params::*;
+pub use poll_decryption_keys::*;
+pub use poll_encryption_keys::*;
@@ -0,0 +1,46 @@
+use crate::rpc::{
+    external::{wait_for_keys, DecryptionKeyEntry},
+    prelude::*,
+};
+
+/// Long-polls for decryption keys. See
+/// [`PollEncryptionKeys`](crate::rpc::external::PollEncryptionKeys) for the
+/// resume semantics.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PollDecryptionKeys {
+    pub from_key_id: KeyId,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PollDecryptionKeysResponse {
+    pub decryption_keys: Vec<DecryptionKeyEntry>,
+}
+
+impl RpcParameter<AppState> for PollDecryptionKeys {
+    type Response = PollDecryptionKeysResponse;
+
+    fn method() -> &'static str {
+        "poll_decryption_keys"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let decryption_keys = wait_for_keys(
+            &context,
+            self.from_key_id,
+            self.timeout_ms,
+            |key_event| matches!(key_event, KeyEvent::DecryptionKeyReady { .. }),
+            |key_id| {
+                DecryptionKey::get(key_id)
+                    .ok()
+                    .map(|decryption_key| DecryptionKeyEntry {
+                        key_id,
+                        decryption_key: Some(decryption_key.as_string()),
+                    })
+            },
+        )
+        .await?;
+
+        Ok(PollDecryptionKeysResponse { decryption_keys })
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use tokio::{
+    sync::broadcast::error::RecvError,
+    time::{timeout_at, Instant},
+};
+
+use crate::rpc::{
+    external::{EncryptionKeyEntry, MAX_KEY_ID_RANGE},
+    prelude::*,
+};
+
+const DEFAULT_POLL_TIMEOUT_MS: u64 = 30_000;
+const MAX_POLL_TIMEOUT_MS: u64 = 60_000;
+
+/// Long-polls for encryption keys. Returns immediately with the consecutive
+/// stored keys starting at `from_key_id`; otherwise holds the request until one
+/// is aggregated or `timeout_ms` elapses, in which case the list is empty.
+///
+/// Clients resume by passing the last key id they received plus one.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PollEncryptionKeys {
+    pub from_key_id: KeyId,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PollEncryptionKeysResponse {
+    pub encryption_keys: Vec<EncryptionKeyEntry>,
+}
+
+impl RpcParameter<AppState> for PollEncryptionKeys {
+    type Response = PollEncryptionKeysResponse;
+
+    fn method() -> &'static str {
+        "poll_encryption_keys"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let encryption_keys = wait_for_keys(
+            &context,
+            self.from_key_id,
+            self.timeout_ms,
+            |key_event| matches!(key_event, KeyEvent::EncryptionKeyReady { .. }),
+            |key_id| {
+                AggregatedKey::get(key_id)
+                    .ok()
+                    .map(|aggregated_key| EncryptionKeyEntry {
+                        key_id,
+                        encryption_key: Some(aggregated_key.encryption_key()),
+                    })
+            },
+        )
+        .await?;
+
+        Ok(PollEncryptionKeysResponse { encryption_keys })
+    }
+}
+
+/// Shared long-poll loop for the `poll_*_keys` methods.
+///
+/// The subscription is taken before the database is read so that a key
+/// stored in between is never missed.
+pub(crate) async fn wait_for_keys<T>(
+    context: &AppState,
+    from_key_id: KeyId,
+    timeout_ms: Option<u64>,
+    is_relevant: impl Fn(&KeyEvent) -> bool,
+    load: impl Fn(KeyId) -> Option<T>,
+) -> Result<Vec<T>, Error> {
+    let timeout_ms = timeout_ms
+        .unwrap_or(DEFAULT_POLL_TIMEOUT_MS)
+        .min(MAX_POLL_TIMEOUT_MS);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+    let mut receiver = context.subscribe_key_events();
+
+    loop {
+        let keys = load_ready_keys(from_key_id, &load);
+        if !keys.is_empty() {
+            return Ok(keys);
+        }
+
+        loop {
+            match timeout_at(deadline, receiver.recv()).await {
+                Ok(Ok(key_event)) => {
+                    if is_relevant(&key_event)
+                        && key_event.key_id().as_u64() >= from_key_id.as_u64()
+                    {
+                        break;
+                    }
+                }
+                // Missed some events; fall back to reading the database.
+                Ok(Err(RecvError::Lagged(_))) => break,
+                Ok(Err(RecvError::Closed)) | Err(_) => return Ok(Vec::new()),
+            }
+        }
+    }
+}
+
+fn load_ready_keys<T>(from_key_id: KeyId, load: impl Fn(KeyId) -> Option<T>) -> Vec<T> {
+    // Decryption keys are only ever stored for aggregated keys, so the latest
+    // aggregated key id bounds both kinds of key.
+    let latest_key_id = match LatestAggregatedKeyId::get() {
+        Ok(latest_aggregated_key_id) => latest_aggregated_key_id.key_id().as_u64(),
+        Err(_) => return Vec::new(),
+    };
+
+    let from = from_key_id.as_u64();
+    if from > latest_key_id {
+        return Vec::new();
+    }
+
+    let to = latest_key_id.min(from + MAX_KEY_ID_RANGE - 1);
+
+    // Stop at the first key that is not ready yet: clients resume from the
+    // last key id they received plus one and would never see it otherwise.
+    (from..=to).map(KeyId::new).map_while(load).collect()
+}
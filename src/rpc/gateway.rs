@@ -0,0 +1,729 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use radius_sdk::json_rpc::server::RpcParameter;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method,
+};
+use rustls::pki_types::CertificateDer;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpListener,
+    sync::Semaphore,
+};
+use tokio_rustls::TlsAcceptor;
+
+use crate::{
+    database,
+    error::Error,
+    metrics,
+    rpc::cluster::GetKeyGeneratorList,
+    tls::bound_address,
+    types::{KeyGeneratorList, RpcLimits},
+};
+
+const MAX_HEADER_SIZE: usize = 16 * 1024;
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(30);
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time a client gets to send the body once its request head arrived.
+const BODY_READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Poll requests are held upstream for up to a minute, so the upstream gets
+/// that and some slack before the request is answered with `504`.
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(75);
+const LINGER_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_LINGER_DRAIN: usize = 1024 * 1024;
+/// Buckets kept before idle ones are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Headers that only apply to a single connection, so they are neither
+/// forwarded upstream nor passed back to the client.
+const HOP_BY_HOP_HEADERS: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Public front of an RPC server. Optionally terminates TLS, enforces
+/// [`RpcLimits`] and forwards the accepted JSON-RPC requests to
+/// `upstream_url`, where the RPC server listens on the loopback interface.
+/// `OPTIONS` requests (CORS preflights) are forwarded as well, and the
+/// headers of the upstream response are passed back to the client.
+///
+/// Rejected requests get the matching HTTP status (e.g. `429`) and a JSON-RPC
+/// error body, and the connection is closed. Connections beyond
+/// [`RpcLimits::max_connections_per_ip`] are closed right away.
+///
+/// Returns the address the gateway is bound to, which tells the port when
+/// `listen_address` asks for any free one (port `0`).
+pub async fn run_rpc_gateway(
+    listen_address: String,
+    upstream_url: String,
+    limits: RpcLimits,
+    tls: Option<GatewayTls>,
+) -> Result<SocketAddr, Error> {
+    let listener = TcpListener::bind(&listen_address)
+        .await
+        .map_err(Error::RpcGateway)?;
+    let bound_address = listener.local_addr().map_err(Error::RpcGateway)?;
+    let gateway = Arc::new(Gateway::new(upstream_url, limits, tls)?);
+
+    tracing::info!("Successfully started the RPC gateway: {}", bound_address);
+
+    database::spawn(async move {
+        loop {
+            let (stream, peer_address) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(error) => {
+                    tracing::warn!("Failed to accept an RPC connection: {}", error);
+                    continue;
+                }
+            };
+
+            let ip = peer_address.ip();
+            let Some(connection) = Connection::open(&gateway, ip) else {
+                metrics::record_rpc_rejection("too_many_connections");
+                continue;
+            };
+
+            let gateway = gateway.clone();
+            database::spawn(async move {
+                let _connection = connection;
+                let result = match gateway.tls.as_ref() {
+                    None => gateway.handle_connection(stream, ip, None).await,
+                    Some(tls) => match tokio::time::timeout(
+                        TLS_HANDSHAKE_TIMEOUT,
+                        tls.acceptor.accept(stream),
+                    )
+                    .await
+                    .unwrap_or_else(|_| Err(std::io::ErrorKind::TimedOut.into()))
+                    {
+                        Ok(stream) => {
+                            let peer_certificate = stream
+                                .get_ref()
+                                .1
+                                .peer_certificates()
+                                .and_then(|certificates| certificates.first())
+                                .map(|certificate| certificate.clone().into_owned());
+
+                            gateway
+                                .handle_connection(stream, ip, peer_certificate)
+                                .await
+                        }
+                        Err(error) => Err(error),
+                    },
+                };
+
+                if let Err(error) = result {
+                    tracing::debug!("RPC connection failed: {}", error);
+                }
+            });
+        }
+    });
+
+    Ok(bound_address)
+}
+
+/// TLS termination in front of the gateway.
+pub struct GatewayTls {
+    pub acceptor: TlsAcceptor,
+    /// Only clients whose certificate is bound to a registered key generator
+    /// may call methods other than `get_key_generator_list`, which joining
+    /// nodes need before they are registered.
+    pub authorize_peers: bool,
+}
+
+struct Gateway {
+    http_client: reqwest::Client,
+    upstream_url: String,
+    limits: RpcLimits,
+    rate_limiter: Option<RateLimiter>,
+    permits: Arc<Semaphore>,
+    connections: Mutex<HashMap<IpAddr, usize>>,
+    tls: Option<GatewayTls>,
+}
+
+/// An open connection, counted against the limit of its IP address until
+/// dropped.
+struct Connection {
+    gateway: Arc<Gateway>,
+    ip: IpAddr,
+}
+
+impl Connection {
+    fn open(gateway: &Arc<Gateway>, ip: IpAddr) -> Option<Self> {
+        let mut connections = gateway.connections.lock().unwrap();
+        let count = connections.entry(ip).or_default();
+        if *count >= gateway.limits.max_connections_per_ip {
+            return None;
+        }
+        *count += 1;
+
+        Some(Self {
+            gateway: gateway.clone(),
+            ip,
+        })
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let mut connections = self.gateway.connections.lock().unwrap();
+        if let Some(count) = connections.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                connections.remove(&self.ip);
+            }
+        }
+    }
+}
+
+impl Gateway {
+    fn new(
+        upstream_url: String,
+        limits: RpcLimits,
+        tls: Option<GatewayTls>,
+    ) -> Result<Self, Error> {
+        let http_client = reqwest::Client::builder()
+            .timeout(UPSTREAM_TIMEOUT)
+            .build()
+            .map_err(|error| Error::RpcGateway(std::io::Error::other(error)))?;
+        let rate_limiter = limits
+            .rate_limit_per_second
+            .map(|per_second| RateLimiter::new(per_second, limits.rate_limit_burst));
+
+        Ok(Self {
+            http_client,
+            upstream_url,
+            permits: Arc::new(Semaphore::new(limits.max_concurrent_requests)),
+            connections: Mutex::new(HashMap::new()),
+            rate_limiter,
+            limits,
+            tls,
+        })
+    }
+
+    async fn handle_connection<S>(
+        &self,
+        mut stream: S,
+        ip: IpAddr,
+        peer_certificate: Option<CertificateDer<'static>>,
+    ) -> std::io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut buffer = Vec::new();
+
+        loop {
+            let head =
+                match tokio::time::timeout(KEEP_ALIVE_TIMEOUT, read_head(&mut stream, &mut buffer))
+                    .await
+                {
+                    Ok(Ok(Some(head))) => head,
+                    Ok(Ok(None)) | Err(_) => return Ok(()),
+                    Ok(Err(rejection)) => return reject(&mut stream, rejection).await,
+                };
+
+            if let Err(rejection) = self.check(&head, ip) {
+                return reject(&mut stream, rejection).await;
+            }
+
+            // A client that is slow to send its body must not hold a permit.
+            let body = match tokio::time::timeout(
+                BODY_READ_TIMEOUT,
+                read_body(&mut stream, &mut buffer, head.content_length),
+            )
+            .await
+            {
+                Ok(body) => body?,
+                Err(_) => return reject(&mut stream, Rejection::RequestTimeout).await,
+            };
+            if self.tls.as_ref().is_some_and(|tls| tls.authorize_peers)
+                && !is_peer_authorized(&body, peer_certificate.as_ref())
+            {
+                return reject(&mut stream, Rejection::Forbidden).await;
+            }
+            let Ok(_permit) = self.permits.clone().try_acquire_owned() else {
+                return reject(&mut stream, Rejection::Busy).await;
+            };
+
+            let response = self.forward(&head, body).await;
+
+            write_response(
+                &mut stream,
+                &response.status,
+                &response.headers,
+                &response.body,
+                head.keep_alive,
+            )
+            .await?;
+            if !head.keep_alive {
+                return stream.shutdown().await;
+            }
+        }
+    }
+
+    fn check(&self, head: &RequestHead, ip: IpAddr) -> Result<(), Rejection> {
+        if head.method != "POST" && head.method != "OPTIONS" {
+            return Err(Rejection::MethodNotAllowed);
+        }
+
+        // Browsers send CORS preflights without the api key.
+        if head.method == "POST"
+            && !self.limits.api_keys.is_empty()
+            && !head
+                .api_key
+                .as_ref()
+                .is_some_and(|api_key| self.limits.api_keys.contains(api_key))
+        {
+            return Err(Rejection::Unauthorized);
+        }
+
+        if head.content_length > self.limits.max_body_size {
+            return Err(Rejection::BodyTooLarge);
+        }
+
+        if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+            if !rate_limiter.check(ip, Instant::now()) {
+                return Err(Rejection::RateLimited);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn forward(&self, head: &RequestHead, body: Vec<u8>) -> UpstreamResponse {
+        let headers: HeaderMap = head
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+
+        let request = match head.method.as_str() {
+            "OPTIONS" => self
+                .http_client
+                .request(Method::OPTIONS, &self.upstream_url),
+            _ => self
+                .http_client
+                .post(&self.upstream_url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body),
+        };
+
+        match request.headers(headers).send().await {
+            Ok(response) => {
+                let status = response.status().to_string();
+                let headers = response
+                    .headers()
+                    .iter()
+                    .filter(|(name, _)| {
+                        !is_hop_by_hop(name.as_str()) && *name != reqwest::header::CONTENT_LENGTH
+                    })
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                    })
+                    .collect();
+
+                match response.bytes().await {
+                    Ok(body) => UpstreamResponse {
+                        status,
+                        headers,
+                        body: body.to_vec(),
+                    },
+                    Err(error) => upstream_error(error),
+                }
+            }
+            Err(error) => upstream_error(error),
+        }
+    }
+}
+
+/// Whether the client behind `peer_certificate` may send `body`.
+fn is_peer_authorized(body: &[u8], peer_certificate: Option<&CertificateDer<'_>>) -> bool {
+    let methods = requested_methods(body);
+    if !methods.is_empty()
+        && methods
+            .iter()
+            .all(|method| method == GetKeyGeneratorList::method())
+    {
+        return true;
+    }
+
+    let Some(peer_certificate) = peer_certificate else {
+        return false;
+    };
+
+    KeyGeneratorList::get()
+        .map(|key_generator_list| {
+            bound_address(
+                peer_certificate,
+                key_generator_list
+                    .iter()
+                    .map(|key_generator| key_generator.address()),
+            )
+            .is_some()
+        })
+        .unwrap_or(false)
+}
+
+/// Methods of a single or batch JSON-RPC request.
+fn requested_methods(body: &[u8]) -> Vec<String> {
+    let method = |request: &serde_json::Value| {
+        request
+            .get("method")
+            .and_then(|method| method.as_str())
+            .map(str::to_owned)
+    };
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(requests)) => requests.iter().filter_map(method).collect(),
+        Ok(request) => method(&request).into_iter().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+struct UpstreamResponse {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+fn upstream_error(error: reqwest::Error) -> UpstreamResponse {
+    tracing::warn!("Failed to forward an RPC request: {}", error);
+
+    let (status, message) = if error.is_timeout() {
+        ("504 Gateway Timeout", "upstream timed out")
+    } else {
+        ("502 Bad Gateway", "upstream unavailable")
+    };
+
+    UpstreamResponse {
+        status: status.to_owned(),
+        headers: json_headers(),
+        body: error_body(-32603, message),
+    }
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP_HEADERS
+        .iter()
+        .any(|hop_by_hop| name.eq_ignore_ascii_case(hop_by_hop))
+}
+
+/// Token bucket per client IP address.
+pub struct RateLimiter {
+    per_second: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `per_second` requests per second per IP address on average and
+    /// up to `burst` requests at once (at least one).
+    pub fn new(per_second: u32, burst: u32) -> Self {
+        Self {
+            per_second: per_second as f64,
+            burst: burst.max(1) as f64,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for `ip` at `now`. Returns `false` if none is left.
+    pub fn check(&self, ip: IpAddr, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.burst);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(TokenBucket {
+            tokens: self.burst,
+            updated_at: now,
+        });
+        bucket.tokens = self.refill(bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn refill(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+
+        (bucket.tokens + elapsed.as_secs_f64() * self.per_second).min(self.burst)
+    }
+}
+
+struct RequestHead {
+    method: String,
+    content_length: usize,
+    api_key: Option<String>,
+    keep_alive: bool,
+    /// End-to-end headers forwarded upstream.
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Rejection {
+    RateLimited,
+    Busy,
+    BodyTooLarge,
+    Unauthorized,
+    Forbidden,
+    MethodNotAllowed,
+    LengthRequired,
+    HeaderTooLarge,
+    BadRequest,
+    RequestTimeout,
+}
+
+impl Rejection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::Busy => "busy",
+            Self::BodyTooLarge => "body_too_large",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::MethodNotAllowed => "method_not_allowed",
+            Self::LengthRequired => "length_required",
+            Self::HeaderTooLarge => "header_too_large",
+            Self::BadRequest => "bad_request",
+            Self::RequestTimeout => "request_timeout",
+        }
+    }
+
+    /// HTTP status, JSON-RPC error code and message.
+    fn response(&self) -> (&'static str, i64, &'static str) {
+        match self {
+            Self::RateLimited => ("429 Too Many Requests", -32005, "rate limit exceeded"),
+            Self::Busy => (
+                "429 Too Many Requests",
+                -32005,
+                "too many concurrent requests",
+            ),
+            Self::BodyTooLarge => ("413 Payload Too Large", -32600, "request body too large"),
+            Self::Unauthorized => ("401 Unauthorized", -32001, "missing or unknown api key"),
+            Self::Forbidden => (
+                "403 Forbidden",
+                -32003,
+                "peer is not a registered key generator",
+            ),
+            Self::MethodNotAllowed => (
+                "405 Method Not Allowed",
+                -32600,
+                "only POST and OPTIONS are served",
+            ),
+            Self::LengthRequired => ("411 Length Required", -32600, "content-length required"),
+            Self::HeaderTooLarge => (
+                "431 Request Header Fields Too Large",
+                -32600,
+                "request header too large",
+            ),
+            Self::BadRequest => ("400 Bad Request", -32700, "malformed http request"),
+            Self::RequestTimeout => ("408 Request Timeout", -32600, "request body too slow"),
+        }
+    }
+}
+
+/// Reads up to the end of the next request head, leaving any bytes after it
+/// in `buffer`. Returns `None` once the client closed the connection.
+async fn read_head<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+) -> Result<Option<RequestHead>, Rejection> {
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+        if buffer.len() > MAX_HEADER_SIZE {
+            return Err(Rejection::HeaderTooLarge);
+        }
+
+        let mut chunk = [0u8; 4096];
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) if buffer.is_empty() => return Ok(None),
+            Ok(0) | Err(_) => return Err(Rejection::BadRequest),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+        }
+    };
+
+    let head: Vec<u8> = buffer.drain(..head_end).collect();
+    let head = std::str::from_utf8(&head).map_err(|_| Rejection::BadRequest)?;
+
+    parse_head(head).map(Some)
+}
+
+fn parse_head(head: &str) -> Result<RequestHead, Rejection> {
+    let mut lines = head.lines();
+    let mut request_line = lines
+        .next()
+        .ok_or(Rejection::BadRequest)?
+        .split_whitespace();
+    let method = request_line.next().ok_or(Rejection::BadRequest)?.to_owned();
+    let _path = request_line.next().ok_or(Rejection::BadRequest)?;
+    let version = request_line.next().ok_or(Rejection::BadRequest)?;
+
+    let mut content_length = None;
+    let mut api_key = None;
+    let mut keep_alive = version == "HTTP/1.1";
+    let mut headers = Vec::new();
+
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        let name = name.trim();
+
+        match name.to_ascii_lowercase().as_str() {
+            // Conflicting lengths would let the gateway and the upstream
+            // disagree on where the body ends.
+            "content-length" if content_length.is_some() => return Err(Rejection::BadRequest),
+            "content-length" => {
+                content_length = Some(value.parse().map_err(|_| Rejection::BadRequest)?);
+            }
+            "transfer-encoding" => return Err(Rejection::LengthRequired),
+            "x-api-key" => api_key = Some(value.to_owned()),
+            "authorization" => {
+                if let Some(token) = value.strip_prefix("Bearer ") {
+                    api_key = Some(token.trim().to_owned());
+                }
+            }
+            "connection" => {
+                if value.eq_ignore_ascii_case("close") {
+                    keep_alive = false;
+                } else if value.eq_ignore_ascii_case("keep-alive") {
+                    keep_alive = true;
+                }
+            }
+            "host" | "content-type" => {}
+            lowercase_name if is_hop_by_hop(lowercase_name) => {}
+            _ => headers.push((name.to_owned(), value.to_owned())),
+        }
+    }
+
+    let content_length = match (method.as_str(), content_length) {
+        (_, Some(content_length)) => content_length,
+        ("POST", None) => return Err(Rejection::LengthRequired),
+        (_, None) => 0,
+    };
+
+    Ok(RequestHead {
+        method,
+        content_length,
+        api_key,
+        keep_alive,
+        headers,
+    })
+}
+
+async fn read_body<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+    content_length: usize,
+) -> std::io::Result<Vec<u8>> {
+    while buffer.len() < content_length {
+        let mut chunk = [0u8; 4096];
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok(buffer.drain(..content_length).collect())
+}
+
+async fn reject<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    rejection: Rejection,
+) -> std::io::Result<()> {
+    metrics::record_rpc_rejection(rejection.as_str());
+
+    let (status, code, message) = rejection.response();
+    write_response(
+        stream,
+        status,
+        &json_headers(),
+        &error_body(code, message),
+        false,
+    )
+    .await?;
+    stream.shutdown().await?;
+
+    // Closing with unread input resets the connection, which may discard the
+    // response before the client reads it. Drain what is left for a moment.
+    let _ = tokio::time::timeout(LINGER_TIMEOUT, async {
+        let mut chunk = [0u8; 4096];
+        let mut drained = 0;
+        while drained < MAX_LINGER_DRAIN {
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => drained += read,
+            }
+        }
+    })
+    .await;
+
+    Ok(())
+}
+
+fn json_headers() -> Vec<(String, String)> {
+    vec![("Content-Type".to_owned(), "application/json".to_owned())]
+}
+
+fn error_body(code: i64, message: &str) -> Vec<u8> {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": null,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+async fn write_response<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    status: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+    keep_alive: bool,
+) -> std::io::Result<()> {
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    let mut head = format!("HTTP/1.1 {}\r\n", status);
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: {}\r\n\r\n",
+        body.len(),
+        connection
+    ));
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await
+}
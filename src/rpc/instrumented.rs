@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use tracing::Instrument;
+
+use crate::{metrics, rpc::prelude::*};
+
+/// Wraps an RPC method so that every call is counted, timed and logged within
+/// a span under the method name of `P`. Serializes exactly like `P`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Instrumented<P>(P);
+
+impl<P> RpcParameter<AppState> for Instrumented<P>
+where
+    P: RpcParameter<AppState>,
+{
+    type Response = P::Response;
+
+    fn method() -> &'static str {
+        P::method()
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        let started_at = Instant::now();
+        let response = self
+            .0
+            .handler(context)
+            .instrument(tracing::info_span!("rpc", method = P::method()))
+            .await;
+
+        metrics::record_rpc_request(P::method(), response.is_ok(), started_at.elapsed());
+
+        response
+    }
+}
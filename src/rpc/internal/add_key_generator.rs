@@ -1,6 +1,6 @@
 use radius_sdk::signature::Address;
 
-use crate::rpc::{cluster::SyncKeyGenerator, prelude::*};
+use crate::rpc::{cluster::SyncKeyGenerator, prelude::*, spawn_multicast};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AddKeyGenerator {
@@ -15,6 +15,18 @@ struct AddKeyGeneratorMessage {
     external_rpc_url: String,
 }
 
+impl AddKeyGenerator {
+    pub fn new(address: Address, cluster_rpc_url: String, external_rpc_url: String) -> Self {
+        Self {
+            message: AddKeyGeneratorMessage {
+                address,
+                cluster_rpc_url,
+                external_rpc_url,
+            },
+        }
+    }
+}
+
 impl RpcParameter<AppState> for AddKeyGenerator {
     type Response = ();
 
@@ -22,7 +34,12 @@ impl RpcParameter<AppState> for AddKeyGenerator {
         "add_key_generator"
     }
 
-    async fn handler(self, _context: AppState) -> Result<Self::Response, RpcError> {
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        // The registry contract is the only source of membership when set.
+        if context.config().registry_contract_address().is_some() {
+            return Err(Error::MembershipManagedByRegistry.into());
+        }
+
         tracing::info!(
             "Add distributed key generation - address: {:?} / cluster_rpc_url: {:?} / external_rpc_url: {:?}",
             self.message.address.as_hex_string(),
@@ -52,34 +69,36 @@ impl RpcParameter<AppState> for AddKeyGenerator {
             key_generator_list.insert(key_generator);
         })?;
 
-        sync_key_generator(self);
+        sync_key_generator(&context, self)?;
 
         Ok(())
     }
 }
 
-pub fn sync_key_generator(add_key_generator: AddKeyGenerator) {
-    let other_key_generator_rpc_url_list = KeyGeneratorList::get()
-        .unwrap()
-        .get_all_key_generator_rpc_url_list();
+pub fn sync_key_generator(
+    context: &AppState,
+    add_key_generator: AddKeyGenerator,
+) -> Result<(), Error> {
+    let other_key_generator_rpc_url_list =
+        KeyGeneratorList::get()?.get_all_key_generator_rpc_url_list();
 
-    tokio::spawn(async move {
-        tracing::info!(
-            "Sync distributed key generation - address: {:?} / cluster_rpc_url: {:?} / rpc_client_count: {:?}",
-            add_key_generator.message.address.as_hex_string(),
-            add_key_generator.message.cluster_rpc_url,
-            other_key_generator_rpc_url_list.len()
-        );
+    tracing::info!(
+        "Sync distributed key generation - address: {:?} / cluster_rpc_url: {:?} / rpc_client_count: {:?}",
+        add_key_generator.message.address.as_hex_string(),
+        add_key_generator.message.cluster_rpc_url,
+        other_key_generator_rpc_url_list.len()
+    );
+
+    let message = add_key_generator.message;
+    spawn_multicast(
+        context,
+        other_key_generator_rpc_url_list,
+        SyncKeyGenerator::new(
+            message.address,
+            message.cluster_rpc_url,
+            message.external_rpc_url,
+        ),
+    );
 
-        let rpc_client = RpcClient::new().unwrap();
-        rpc_client
-            .multicast(
-                other_key_generator_rpc_url_list,
-                SyncKeyGenerator::method(),
-                &add_key_generator,
-                Id::Null,
-            )
-            .await
-            .unwrap();
-    });
+    Ok(())
 }
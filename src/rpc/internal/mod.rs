@@ -1,4 +1,7 @@
 mod add_key_generator;
+mod trigger_round;
+
 pub use add_key_generator::*;
+pub use trigger_round::*;
 
 pub mod debug;
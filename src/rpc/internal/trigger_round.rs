@@ -0,0 +1,28 @@
+use crate::rpc::prelude::*;
+
+/// Starts a key generation round on a leader using the `external` round
+/// schedule. Ignored by other schedules.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TriggerRound {}
+
+impl RpcParameter<AppState> for TriggerRound {
+    type Response = ();
+
+    fn method() -> &'static str {
+        "trigger_round"
+    }
+
+    async fn handler(self, context: AppState) -> Result<Self::Response, RpcError> {
+        if context.config().round_schedule() != RoundSchedule::External {
+            tracing::warn!(
+                "Ignored trigger_round - round schedule: {:?}",
+                context.config().round_schedule()
+            );
+            return Ok(());
+        }
+
+        context.round_trigger().notify_one();
+
+        Ok(())
+    }
+}
@@ -1,11 +1,23 @@
 pub mod cluster;
 
 pub mod external;
+mod gateway;
+mod instrumented;
 pub mod internal;
+mod multicast;
+pub mod transport;
+
+pub use gateway::{run_rpc_gateway, GatewayTls, RateLimiter};
+pub use instrumented::Instrumented;
+pub use multicast::spawn_multicast;
+
 pub mod prelude {
-    pub use radius_sdk::json_rpc::{
-        client::{Id, RpcClient},
-        server::{RpcError, RpcParameter},
+    pub use radius_sdk::{
+        json_rpc::{
+            client::{Id, RpcClient},
+            server::{RpcError, RpcParameter},
+        },
+        signature::{ChainType, SignatureError},
     };
     pub use serde::{Deserialize, Serialize};
 
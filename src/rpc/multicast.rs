@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use tracing::Instrument;
+
+use crate::{
+    database, metrics,
+    rpc::prelude::*,
+    state::{DeliveryOutcome, PeerDelivery},
+};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_DELIVERY_RETRIES: u32 = 3;
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Methods whose handlers ignore a repeated delivery. Only these are sent
+/// again after a timeout or a connection failure, since the first attempt may
+/// have been handled anyway.
+const IDEMPOTENT_METHODS: [&str; 6] = [
+    "heartbeat",
+    "sync_key_generator",
+    "run_generate_partial_key",
+    "sync_partial_key",
+    "sync_aggregated_key",
+    "sync_aggregated_key_signature",
+];
+
+/// Sends `parameter` to every url in the background. Each peer gets its own
+/// task with a per-request timeout and, for idempotent methods, bounded
+/// retries on transport failures, so a slow or dead peer neither delays nor
+/// fails the delivery to the others. The final outcome per peer is recorded
+/// in metrics and in [`AppState`] for the status endpoint.
+pub fn spawn_multicast<P>(context: &AppState, rpc_urls: Vec<String>, parameter: P)
+where
+    P: RpcParameter<AppState> + Send + Sync + 'static,
+    P::Response: DeserializeOwned,
+{
+    let parameter = match serde_json::to_value(&parameter) {
+        Ok(parameter) => parameter,
+        Err(error) => {
+            metrics::record_multicast_failure(P::method());
+            tracing::error!("Failed to multicast {} - error: {}", P::method(), error);
+            return;
+        }
+    };
+
+    for rpc_url in rpc_urls {
+        let context = context.clone();
+        let parameter = parameter.clone();
+
+        database::spawn(
+            async move {
+                let peer_delivery = deliver::<P>(&context, &rpc_url, parameter).await;
+
+                metrics::record_multicast_delivery(P::method(), peer_delivery.outcome.as_str());
+                if peer_delivery.outcome != DeliveryOutcome::Delivered {
+                    metrics::record_multicast_failure(P::method());
+                    tracing::warn!(
+                        "Failed to deliver {} to {} after {} attempt(s) - outcome: {:?}",
+                        P::method(),
+                        rpc_url,
+                        peer_delivery.attempts,
+                        peer_delivery.outcome,
+                    );
+                }
+
+                context.record_peer_delivery(rpc_url, peer_delivery);
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+async fn deliver<P>(context: &AppState, rpc_url: &str, parameter: serde_json::Value) -> PeerDelivery
+where
+    P: RpcParameter<AppState>,
+    P::Response: DeserializeOwned,
+{
+    let max_attempts = if IDEMPOTENT_METHODS.contains(&P::method()) {
+        MAX_DELIVERY_RETRIES + 1
+    } else {
+        1
+    };
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    let mut outcome = DeliveryOutcome::Failed;
+
+    for attempt in 0..max_attempts {
+        if attempt > 0 {
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+        }
+
+        let response = tokio::time::timeout(
+            DELIVERY_TIMEOUT,
+            context
+                .cluster_transport()
+                .send(rpc_url, P::method(), parameter.clone()),
+        )
+        .await;
+
+        let (attempt_outcome, is_transient) = match response {
+            Ok(Ok(response)) => match serde_json::from_value::<P::Response>(response) {
+                Ok(_) => {
+                    return PeerDelivery::new(P::method(), DeliveryOutcome::Delivered, attempt + 1)
+                }
+                Err(error) => {
+                    tracing::debug!("{} answered badly at {}: {}", P::method(), rpc_url, error);
+                    (DeliveryOutcome::Failed, false)
+                }
+            },
+            Ok(Err(error)) => {
+                tracing::debug!("{} failed at {}: {}", P::method(), rpc_url, error);
+                (DeliveryOutcome::Failed, error.is_transient())
+            }
+            Err(_) => {
+                tracing::debug!("{} timed out at {}", P::method(), rpc_url);
+                (DeliveryOutcome::TimedOut, true)
+            }
+        };
+        outcome = attempt_outcome;
+
+        // The peer answered; it would answer the same way again.
+        if !is_transient {
+            return PeerDelivery::new(P::method(), outcome, attempt + 1);
+        }
+    }
+
+    PeerDelivery::new(P::method(), outcome, max_attempts)
+}
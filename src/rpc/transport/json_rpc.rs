@@ -0,0 +1,37 @@
+use reqwest::{Client, Url};
+
+use super::{post_json_rpc, ClusterTransport, TransportError, TransportFuture};
+
+/// Sends cluster RPCs as plain JSON-RPC over HTTP.
+pub struct JsonRpcTransport {
+    http_client: Client,
+}
+
+impl JsonRpcTransport {
+    pub fn new() -> Result<Self, TransportError> {
+        Ok(Self {
+            http_client: Client::builder().build().map_err(TransportError::Http)?,
+        })
+    }
+
+    /// Sends through `http_client`, e.g. one with default headers or extra
+    /// root certificates.
+    pub fn with_http_client(http_client: Client) -> Self {
+        Self { http_client }
+    }
+}
+
+impl ClusterTransport for JsonRpcTransport {
+    fn send<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        method: &'static str,
+        parameter: serde_json::Value,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let url = Url::parse(rpc_url).map_err(|_| TransportError::Unreachable)?;
+
+            post_json_rpc(&self.http_client, url, method, parameter).await
+        })
+    }
+}
@@ -0,0 +1,112 @@
+mod json_rpc;
+mod simulated;
+mod tls;
+
+use std::{future::Future, pin::Pin};
+
+pub use json_rpc::*;
+use radius_sdk::json_rpc::server::RpcParameter;
+use serde::de::DeserializeOwned;
+pub use simulated::*;
+pub use tls::*;
+
+use crate::state::AppState;
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<serde_json::Value, TransportError>> + Send + 'a>>;
+
+/// Carries cluster RPCs between key generators. Parameters and responses
+/// travel as JSON so that implementations do not need to know the RPC types.
+///
+/// [`JsonRpcTransport`] is used in production, or [`TlsTransport`] when the
+/// cluster runs over mutual TLS. [`SimulatedNetwork`] lets tests inject
+/// faults deterministically.
+pub trait ClusterTransport: Send + Sync {
+    fn send<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        method: &'static str,
+        parameter: serde_json::Value,
+    ) -> TransportFuture<'a>;
+}
+
+/// Sends a single typed RPC through `transport`.
+pub async fn request<P>(
+    transport: &dyn ClusterTransport,
+    rpc_url: &str,
+    parameter: &P,
+) -> Result<P::Response, TransportError>
+where
+    P: RpcParameter<AppState>,
+    P::Response: DeserializeOwned,
+{
+    let parameter = serde_json::to_value(parameter).map_err(TransportError::Serialize)?;
+    let response = transport.send(rpc_url, P::method(), parameter).await?;
+
+    serde_json::from_value(response).map_err(TransportError::Serialize)
+}
+
+/// Posts a JSON-RPC request to `url`, telling an error answered by the peer
+/// ([`TransportError::Rejected`]) apart from a failure to reach it.
+pub(crate) async fn post_json_rpc(
+    http_client: &reqwest::Client,
+    url: reqwest::Url,
+    method: &'static str,
+    parameter: serde_json::Value,
+) -> Result<serde_json::Value, TransportError> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": parameter,
+        "id": null,
+    });
+
+    let mut response: serde_json::Value = http_client
+        .post(url)
+        .json(&request)
+        .send()
+        .await
+        .map_err(TransportError::Http)?
+        .json()
+        .await
+        .map_err(TransportError::Http)?;
+
+    match response.get("error") {
+        Some(error) if !error.is_null() => Err(TransportError::Rejected(error.to_string())),
+        _ => Ok(response
+            .get_mut("result")
+            .map(serde_json::Value::take)
+            .unwrap_or_default()),
+    }
+}
+
+#[derive(Debug)]
+pub enum TransportError {
+    Http(reqwest::Error),
+    Serialize(serde_json::Error),
+    /// No route to the peer, e.g. because of a simulated partition.
+    Unreachable,
+    /// The peer handled the request and answered with an error.
+    Rejected(String),
+}
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl TransportError {
+    /// Whether the request may not have reached the peer, so that sending it
+    /// again can help. A peer that answered with an error would answer the
+    /// same way again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Http(error) => !error.is_decode() && !error.is_status(),
+            Self::Unreachable => true,
+            Self::Serialize(_) | Self::Rejected(_) => false,
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::{ClusterTransport, TransportError, TransportFuture};
+
+pub type SimulatedHandler = Arc<
+    dyn Fn(
+            &'static str,
+            serde_json::Value,
+        ) -> Pin<Box<dyn Future<Output = Result<serde_json::Value, String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Faults applied to every message on a [`SimulatedNetwork`].
+#[derive(Clone, Debug, Default)]
+pub struct FaultConfig {
+    /// Probability that a message is lost. The sender then waits until its
+    /// own timeout fires, as it would on a real network.
+    pub drop_probability: f64,
+    /// Probability that a message is delivered twice.
+    pub duplicate_probability: f64,
+    /// Every message is delayed by a random duration up to this bound, which
+    /// also reorders messages sent close together.
+    pub max_delay: Duration,
+}
+
+/// In-memory network between key generators identified by their cluster RPC
+/// urls. Every random decision comes from a single seeded RNG, so a test run
+/// under a paused tokio clock (`#[tokio::test(start_paused = true)]`) is
+/// reproducible from its seed.
+#[derive(Clone)]
+pub struct SimulatedNetwork {
+    inner: Arc<SimulatedNetworkInner>,
+}
+
+struct SimulatedNetworkInner {
+    rng: Mutex<ChaCha8Rng>,
+    fault_config: Mutex<FaultConfig>,
+    handlers: Mutex<HashMap<String, SimulatedHandler>>,
+    partitions: Mutex<HashSet<(String, String)>>,
+}
+
+impl SimulatedNetwork {
+    pub fn new(seed: u64, fault_config: FaultConfig) -> Self {
+        Self {
+            inner: Arc::new(SimulatedNetworkInner {
+                rng: Mutex::new(ChaCha8Rng::seed_from_u64(seed)),
+                fault_config: Mutex::new(fault_config),
+                handlers: Mutex::new(HashMap::new()),
+                partitions: Mutex::new(HashSet::new()),
+            }),
+        }
+    }
+
+    /// Makes `rpc_url` reachable, answering every request with `handler`.
+    pub fn register(&self, rpc_url: impl Into<String>, handler: SimulatedHandler) {
+        self.inner
+            .handlers
+            .lock()
+            .unwrap()
+            .insert(rpc_url.into(), handler);
+    }
+
+    /// Takes `rpc_url` off the network, as if the node crashed.
+    pub fn unregister(&self, rpc_url: &str) {
+        self.inner.handlers.lock().unwrap().remove(rpc_url);
+    }
+
+    pub fn set_fault_config(&self, fault_config: FaultConfig) {
+        *self.inner.fault_config.lock().unwrap() = fault_config;
+    }
+
+    /// Cuts every link between the two groups, in both directions.
+    pub fn partition(&self, group_a: &[&str], group_b: &[&str]) {
+        let mut partitions = self.inner.partitions.lock().unwrap();
+        for a in group_a {
+            for b in group_b {
+                partitions.insert((a.to_string(), b.to_string()));
+                partitions.insert((b.to_string(), a.to_string()));
+            }
+        }
+    }
+
+    pub fn heal(&self) {
+        self.inner.partitions.lock().unwrap().clear();
+    }
+
+    /// Returns the transport used by the node listening on `local_rpc_url`.
+    pub fn transport(&self, local_rpc_url: impl Into<String>) -> SimulatedTransport {
+        SimulatedTransport {
+            network: self.clone(),
+            local_rpc_url: local_rpc_url.into(),
+        }
+    }
+
+    fn is_partitioned(&self, from: &str, to: &str) -> bool {
+        self.inner
+            .partitions
+            .lock()
+            .unwrap()
+            .contains(&(from.to_owned(), to.to_owned()))
+    }
+
+    /// Decides the fate of one message: `None` if it is dropped, otherwise
+    /// the delays of its deliveries.
+    fn roll(&self) -> Option<Vec<Duration>> {
+        let fault_config = self.inner.fault_config.lock().unwrap().clone();
+        let mut rng = self.inner.rng.lock().unwrap();
+
+        if rng.gen_bool(fault_config.drop_probability) {
+            return None;
+        }
+
+        let delivery_count = if rng.gen_bool(fault_config.duplicate_probability) {
+            2
+        } else {
+            1
+        };
+
+        let max_delay_ms = fault_config.max_delay.as_millis() as u64;
+        Some(
+            (0..delivery_count)
+                .map(|_| Duration::from_millis(rng.gen_range(0..=max_delay_ms)))
+                .collect(),
+        )
+    }
+}
+
+/// [`ClusterTransport`] of a single node on a [`SimulatedNetwork`].
+#[derive(Clone)]
+pub struct SimulatedTransport {
+    network: SimulatedNetwork,
+    local_rpc_url: String,
+}
+
+impl ClusterTransport for SimulatedTransport {
+    fn send<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        method: &'static str,
+        parameter: serde_json::Value,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            if self.network.is_partitioned(&self.local_rpc_url, rpc_url) {
+                return Err(TransportError::Unreachable);
+            }
+
+            let handler = self
+                .network
+                .inner
+                .handlers
+                .lock()
+                .unwrap()
+                .get(rpc_url)
+                .cloned()
+                .ok_or(TransportError::Unreachable)?;
+
+            let Some(delays) = self.network.roll() else {
+                return std::future::pending().await;
+            };
+
+            // A duplicate is delivered on its own; only the first delivery
+            // answers the sender.
+            for delay in delays.iter().skip(1).copied() {
+                let handler = handler.clone();
+                let parameter = parameter.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    let _ = handler(method, parameter).await;
+                });
+            }
+
+            tokio::time::sleep(delays[0]).await;
+            handler(method, parameter)
+                .await
+                .map_err(TransportError::Rejected)
+        })
+    }
+}
@@ -0,0 +1,141 @@
+use std::{collections::HashMap, fs, sync::Mutex};
+
+use reqwest::{Certificate, Client, Identity, Url};
+
+use super::{post_json_rpc, ClusterTransport, TransportError, TransportFuture};
+use crate::{
+    tls::{address_server_name, TlsError},
+    types::{KeyGeneratorList, TlsFiles},
+};
+
+/// Sends cluster RPCs over mutual TLS. The node presents its own cluster
+/// certificate and only accepts a peer whose certificate is issued by the
+/// cluster CA and bound to the address the peer is registered with.
+///
+/// Urls that belong to no registered key generator, such as the seed node
+/// before this node joined, are only checked against the cluster CA.
+///
+/// The client of a registered peer is pinned to the address its host
+/// resolved to, and dropped when a request through it cannot reach the peer,
+/// so the next request resolves the host again.
+pub struct TlsTransport {
+    identity: Identity,
+    ca_certificates: Vec<Certificate>,
+    unbound_client: Client,
+    /// Clients pinned to a peer, by cluster RPC url and address name.
+    bound_clients: Mutex<HashMap<(String, String), Client>>,
+}
+
+impl TlsTransport {
+    pub fn new(tls_files: &TlsFiles) -> Result<Self, TlsError> {
+        let mut identity_pem = fs::read(&tls_files.cert_path).map_err(TlsError::Io)?;
+        identity_pem.extend(fs::read(&tls_files.key_path).map_err(TlsError::Io)?);
+        let identity = Identity::from_pem(&identity_pem).map_err(TlsError::Http)?;
+
+        let ca_pem = fs::read(&tls_files.ca_path).map_err(TlsError::Io)?;
+        let ca_certificates = Certificate::from_pem_bundle(&ca_pem).map_err(TlsError::Http)?;
+
+        let unbound_client = client_builder(&identity, &ca_certificates)
+            .danger_accept_invalid_hostnames(true)
+            .build()
+            .map_err(TlsError::Http)?;
+
+        Ok(Self {
+            identity,
+            ca_certificates,
+            unbound_client,
+            bound_clients: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The client and url to reach `rpc_url` with, and the key the client is
+    /// cached under. For a registered peer the url names its address, which
+    /// the client resolves to the real host, so that the usual hostname check
+    /// enforces the address binding.
+    async fn route(
+        &self,
+        rpc_url: &str,
+    ) -> Result<(Client, Url, Option<(String, String)>), TransportError> {
+        let mut url = Url::parse(rpc_url).map_err(|_| TransportError::Unreachable)?;
+
+        let key_generator = KeyGeneratorList::get().ok().and_then(|key_generator_list| {
+            key_generator_list
+                .iter()
+                .find(|key_generator| key_generator.cluster_rpc_url() == rpc_url)
+                .cloned()
+        });
+        let Some(key_generator) = key_generator else {
+            return Ok((self.unbound_client.clone(), url, None));
+        };
+
+        let server_name = address_server_name(key_generator.address());
+        let client_key = (rpc_url.to_owned(), server_name.clone());
+        let cached_client = self.bound_clients.lock().unwrap().get(&client_key).cloned();
+        let client = match cached_client {
+            Some(client) => client,
+            None => {
+                let host = url.host_str().ok_or(TransportError::Unreachable)?;
+                let port = url
+                    .port_or_known_default()
+                    .ok_or(TransportError::Unreachable)?;
+                let socket_address = tokio::net::lookup_host((host, port))
+                    .await
+                    .ok()
+                    .and_then(|mut socket_addresses| socket_addresses.next())
+                    .ok_or(TransportError::Unreachable)?;
+
+                let client = client_builder(&self.identity, &self.ca_certificates)
+                    .resolve(&server_name, socket_address)
+                    .build()
+                    .map_err(TransportError::Http)?;
+
+                self.bound_clients
+                    .lock()
+                    .unwrap()
+                    .insert(client_key.clone(), client.clone());
+
+                client
+            }
+        };
+
+        url.set_host(Some(&server_name))
+            .map_err(|_| TransportError::Unreachable)?;
+
+        Ok((client, url, Some(client_key)))
+    }
+}
+
+fn client_builder(identity: &Identity, ca_certificates: &[Certificate]) -> reqwest::ClientBuilder {
+    ca_certificates.iter().fold(
+        Client::builder()
+            .use_rustls_tls()
+            .tls_built_in_root_certs(false)
+            .identity(identity.clone()),
+        |client_builder, ca_certificate| {
+            client_builder.add_root_certificate(ca_certificate.clone())
+        },
+    )
+}
+
+impl ClusterTransport for TlsTransport {
+    fn send<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        method: &'static str,
+        parameter: serde_json::Value,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let (client, url, client_key) = self.route(rpc_url).await?;
+
+            let result = post_json_rpc(&client, url, method, parameter).await;
+            if let (Err(error), Some(client_key)) = (&result, client_key) {
+                // The peer may have moved to another host address.
+                if error.is_transient() {
+                    self.bound_clients.lock().unwrap().remove(&client_key);
+                }
+            }
+
+            result
+        })
+    }
+}
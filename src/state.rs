@@ -1,6 +1,25 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
-use crate::types::Config;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Notify};
+
+use crate::{
+    error::Error,
+    metrics,
+    rpc::transport::ClusterTransport,
+    task::webhook::queue_webhook_deliveries,
+    types::{skde_params_hash, Config, KeyEvent, KeyId, KeySignature, KeySignatureMessage},
+};
+
+/// Number of key events buffered for slow subscribers before they lag.
+const KEY_EVENT_CHANNEL_CAPACITY: usize = 1024;
 
 pub struct AppState {
     inner: Arc<AppStateInner>,
@@ -9,6 +28,15 @@ pub struct AppState {
 struct AppStateInner {
     config: Config,
     skde_params: skde::delay_encryption::SkdeParams,
+    skde_params_hash: String,
+    key_event_sender: broadcast::Sender<KeyEvent>,
+    cluster_transport: Arc<dyn ClusterTransport>,
+    round_trigger: Arc<Notify>,
+    pending_puzzle_count: Arc<AtomicUsize>,
+    rounds_succeeded: AtomicU64,
+    rounds_failed: AtomicU64,
+    peer_deliveries: Mutex<HashMap<String, PeerDelivery>>,
+    started_at: Instant,
 }
 
 unsafe impl Send for AppState {}
@@ -23,16 +51,34 @@ impl Clone for AppState {
 }
 
 impl AppState {
-    pub fn new(config: Config, skde_params: skde::delay_encryption::SkdeParams) -> Self {
+    pub fn new(
+        config: Config,
+        skde_params: skde::delay_encryption::SkdeParams,
+        cluster_transport: Arc<dyn ClusterTransport>,
+    ) -> Result<Self, Error> {
+        let (key_event_sender, _) = broadcast::channel(KEY_EVENT_CHANNEL_CAPACITY);
+
+        let skde_params_hash =
+            skde_params_hash(&skde_params).map_err(Error::SerializeSkdeParams)?;
+
         let inner = AppStateInner {
             config,
 
             skde_params,
+            skde_params_hash,
+            key_event_sender,
+            cluster_transport,
+            round_trigger: Arc::new(Notify::new()),
+            pending_puzzle_count: Arc::new(AtomicUsize::new(0)),
+            rounds_succeeded: AtomicU64::new(0),
+            rounds_failed: AtomicU64::new(0),
+            peer_deliveries: Mutex::new(HashMap::new()),
+            started_at: Instant::now(),
         };
 
-        Self {
+        Ok(Self {
             inner: Arc::new(inner),
-        }
+        })
     }
 
     pub fn config(&self) -> &Config {
@@ -42,4 +88,150 @@ impl AppState {
     pub fn skde_params(&self) -> &skde::delay_encryption::SkdeParams {
         &self.inner.skde_params
     }
+
+    pub fn skde_params_hash(&self) -> &str {
+        &self.inner.skde_params_hash
+    }
+
+    /// Transport used for every multicast to other key generators.
+    pub fn cluster_transport(&self) -> &dyn ClusterTransport {
+        self.inner.cluster_transport.as_ref()
+    }
+
+    /// Notified by `trigger_round` to start a round under the `external`
+    /// round schedule.
+    pub fn round_trigger(&self) -> Arc<Notify> {
+        self.inner.round_trigger.clone()
+    }
+
+    /// Signs `key` for `key_id` with the node's signer so that clients can
+    /// check the response against other key generators.
+    pub fn sign_key(&self, key_id: KeyId, key: String) -> Result<KeySignature, Error> {
+        let message = KeySignatureMessage::new(key_id, key, self.skde_params_hash().to_owned());
+
+        Ok(KeySignature::sign(self.config().signer(), &message)?)
+    }
+
+    /// Queues the webhook deliveries of `key_event` and notifies every
+    /// subscriber about it. Having no subscriber is not an error.
+    pub fn publish_key_event(&self, key_event: KeyEvent) {
+        queue_webhook_deliveries(self, &key_event);
+
+        let _ = self.inner.key_event_sender.send(key_event);
+    }
+
+    pub fn subscribe_key_events(&self) -> broadcast::Receiver<KeyEvent> {
+        self.inner.key_event_sender.subscribe()
+    }
+
+    /// Registers a time-lock puzzle that is being solved. The returned guard
+    /// must be held until the puzzle is solved (or abandoned).
+    pub fn start_puzzle_job(&self) -> PuzzleJob {
+        self.inner
+            .pending_puzzle_count
+            .fetch_add(1, Ordering::Relaxed);
+
+        PuzzleJob(self.inner.pending_puzzle_count.clone())
+    }
+
+    pub fn pending_puzzle_count(&self) -> usize {
+        self.inner.pending_puzzle_count.load(Ordering::Relaxed)
+    }
+
+    pub fn record_round_result(&self, is_ok: bool) {
+        let counter = if is_ok {
+            &self.inner.rounds_succeeded
+        } else {
+            &self.inner.rounds_failed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        metrics::record_round(is_ok);
+    }
+
+    pub fn round_stats(&self) -> RoundStats {
+        RoundStats {
+            succeeded: self.inner.rounds_succeeded.load(Ordering::Relaxed),
+            failed: self.inner.rounds_failed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Remembers how the latest multicast to `cluster_rpc_url` went.
+    pub fn record_peer_delivery(&self, cluster_rpc_url: String, peer_delivery: PeerDelivery) {
+        if let Ok(mut peer_deliveries) = self.inner.peer_deliveries.lock() {
+            peer_deliveries.insert(cluster_rpc_url, peer_delivery);
+        }
+    }
+
+    pub fn peer_delivery(&self, cluster_rpc_url: &str) -> Option<PeerDelivery> {
+        self.inner
+            .peer_deliveries
+            .lock()
+            .ok()?
+            .get(cluster_rpc_url)
+            .cloned()
+    }
+
+    pub fn uptime_secs(&self) -> u64 {
+        self.inner.started_at.elapsed().as_secs()
+    }
+}
+
+/// Rounds driven by this node since it started.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundStats {
+    pub succeeded: u64,
+    pub failed: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryOutcome {
+    Delivered,
+    Failed,
+    TimedOut,
+}
+
+impl DeliveryOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Result of the latest multicast delivery to a single peer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerDelivery {
+    pub method: String,
+    pub outcome: DeliveryOutcome,
+    pub attempts: u32,
+    /// Unix timestamp in seconds.
+    pub finished_at: u64,
+}
+
+impl PeerDelivery {
+    pub fn new(method: &str, outcome: DeliveryOutcome, attempts: u32) -> Self {
+        let finished_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            method: method.to_owned(),
+            outcome,
+            attempts,
+            finished_at,
+        }
+    }
+}
+
+pub struct PuzzleJob(Arc<AtomicUsize>);
+
+impl Drop for PuzzleJob {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use radius_sdk::kvstore::KvStoreError;
+use tokio::task::JoinSet;
+
+use crate::{
+    database,
+    rpc::{cluster::Heartbeat, transport},
+    state::AppState,
+    task::TraceExt,
+    types::*,
+};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Probes every other key generator on its cluster port and persists the
+/// outcome as [`PeerLiveness`], so that rounds can leave out dead peers.
+pub fn run_heartbeat(context: AppState) {
+    database::spawn(async move {
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Some(key_generator_list) = KeyGeneratorList::get().ok_or_trace() {
+                probe_key_generators(&context, key_generator_list).await;
+            }
+        }
+    });
+}
+
+async fn probe_key_generators(context: &AppState, key_generator_list: KeyGeneratorList) {
+    let my_address = context.config().address().clone();
+
+    let mut probes = JoinSet::new();
+    for key_generator in key_generator_list.iter() {
+        if key_generator.address() == &my_address {
+            continue;
+        }
+
+        let key_generator = key_generator.clone();
+        let parameter = Heartbeat {
+            address: my_address.clone(),
+        };
+        let context = context.clone();
+
+        probes.spawn(database::in_current_scope(async move {
+            let started_at = Instant::now();
+            let is_ok = tokio::time::timeout(
+                HEARTBEAT_TIMEOUT,
+                transport::request(
+                    context.cluster_transport(),
+                    key_generator.cluster_rpc_url(),
+                    &parameter,
+                ),
+            )
+            .await
+            .is_ok_and(|response| response.is_ok());
+
+            (key_generator, is_ok, started_at.elapsed())
+        }));
+    }
+
+    while let Some(probe) = probes.join_next().await {
+        let Ok((key_generator, is_ok, rtt)) = probe else {
+            continue;
+        };
+
+        // Do not bring back the liveness of a key generator that left while
+        // it was probed.
+        if !KeyGeneratorList::get()
+            .is_ok_and(|list| list.is_key_generator_in_cluster(key_generator.address()))
+        {
+            continue;
+        }
+
+        update_peer_liveness(&key_generator, is_ok, rtt).ok_or_trace();
+    }
+}
+
+fn update_peer_liveness(
+    key_generator: &KeyGenerator,
+    is_ok: bool,
+    rtt: Duration,
+) -> Result<(), KvStoreError> {
+    let address = key_generator.address();
+    let mut peer_liveness = PeerLiveness::get_or(address, PeerLiveness::default)?;
+    let was_alive = peer_liveness.is_alive();
+
+    if is_ok {
+        peer_liveness.record_success(rtt.as_millis() as u64);
+    } else {
+        peer_liveness.record_failure();
+    }
+
+    match (was_alive, peer_liveness.is_alive()) {
+        (true, false) => tracing::warn!(
+            "Key generator is unreachable - address: {:?} / cluster_rpc_url: {:?}",
+            address.as_hex_string(),
+            key_generator.cluster_rpc_url()
+        ),
+        (false, true) => tracing::info!(
+            "Key generator is reachable again - address: {:?} / cluster_rpc_url: {:?}",
+            address.as_hex_string(),
+            key_generator.cluster_rpc_url()
+        ),
+        _ => {}
+    }
+
+    peer_liveness.put(address)
+}
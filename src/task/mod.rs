@@ -1,6 +1,12 @@
+pub mod heartbeat;
+pub mod publisher;
+pub mod registry;
+pub mod round;
+pub mod scheduler;
 pub mod single_key_generator;
+pub mod webhook;
 
-/// Using unwrap() inside the task block is caught by tracing::error!().
+/// A panic inside a spawned task is only reported by tracing::error!().
 /// However, if the task involves a loop that must not break when panics,
 /// the trait helps to convert `Result<T, E>` to `Option<T>` while printing
 /// the error message to the console.
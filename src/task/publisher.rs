@@ -0,0 +1,104 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use radius_sdk::signature::Address;
+
+use crate::{database, error::Error, publisher::KeyPublisher, task::TraceExt, types::*};
+
+const PUBLISH_TICK: Duration = Duration::from_secs(1);
+const MAX_PUBLISH_ATTEMPTS: u32 = 10;
+const MAX_BACKOFF_SECS: u64 = 300;
+
+/// Stores the commitment of `key_id` as pending and queues it for
+/// [`run_key_publisher`].
+pub fn queue_key_commitment(
+    key_id: KeyId,
+    encryption_key: &str,
+    participants: Vec<Address>,
+) -> Result<(), Error> {
+    let key_commitment = KeyCommitment::new(key_id, encryption_key, participants);
+    PublishStatus::new(key_commitment, now()).put(key_id)?;
+
+    KeyPublishQueue::apply(|key_publish_queue| key_publish_queue.push(key_id))?;
+
+    Ok(())
+}
+
+/// Publishes queued key commitments through `key_publisher`, retrying with
+/// exponential backoff until they are published or run out of attempts. The
+/// outcome is kept as the [`PublishStatus`] of each key id.
+pub fn run_key_publisher(key_publisher: Arc<dyn KeyPublisher>) {
+    database::spawn(async move {
+        let mut interval = tokio::time::interval(PUBLISH_TICK);
+
+        loop {
+            interval.tick().await;
+
+            if let Some(key_publish_queue) = KeyPublishQueue::get().ok_or_trace() {
+                for key_id in key_publish_queue.to_vec() {
+                    publish_due(key_publisher.as_ref(), key_id)
+                        .await
+                        .ok_or_trace();
+                }
+            }
+        }
+    });
+}
+
+async fn publish_due(key_publisher: &dyn KeyPublisher, key_id: KeyId) -> Result<(), Error> {
+    let mut publish_status = PublishStatus::get(key_id)?;
+    if !publish_status.is_due(now()) {
+        return Ok(());
+    }
+
+    match key_publisher.publish(&publish_status.commitment).await {
+        Ok(receipt) => {
+            tracing::info!(
+                "Published key commitment - key_id: {:?} / key_hash: {:?} / receipt: {:?}",
+                key_id,
+                publish_status.commitment.key_hash,
+                receipt
+            );
+            publish_status.record_success(receipt);
+        }
+        Err(error) => {
+            let backoff = 2_u64
+                .saturating_pow(publish_status.attempts)
+                .min(MAX_BACKOFF_SECS);
+            publish_status.record_failure(error.to_string(), now(), backoff, MAX_PUBLISH_ATTEMPTS);
+
+            tracing::warn!(
+                "Failed to publish key commitment - key_id: {:?} / attempts: {} / error: {}",
+                key_id,
+                publish_status.attempts,
+                error
+            );
+            if publish_status.state == PublishState::Failed {
+                tracing::error!(
+                    "Gave up publishing key commitment after {} attempts - key_id: {:?}",
+                    publish_status.attempts,
+                    key_id
+                );
+                key_publisher
+                    .abandon(&publish_status.commitment)
+                    .ok_or_trace();
+            }
+        }
+    }
+
+    publish_status.put(key_id)?;
+    if publish_status.state != PublishState::Pending {
+        KeyPublishQueue::apply(|key_publish_queue| key_publish_queue.remove(key_id))?;
+    }
+
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::{
+    chain::ChainClient, database, error::Error, registry::fetch_registered_key_generators,
+    state::AppState, task::TraceExt, types::*,
+};
+
+const REGISTRY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps [`KeyGeneratorList`] in line with the registry contract, reading it
+/// again every `registry_sync_interval_blocks` blocks. Does nothing unless a
+/// registry contract is configured.
+pub fn run_registry_sync(context: AppState) {
+    let (Some(chain_rpc_url), Some(contract_address)) = (
+        context.config().chain_rpc_url().clone(),
+        context.config().registry_contract_address().clone(),
+    ) else {
+        return;
+    };
+    let sync_interval_blocks = context.config().registry_sync_interval_blocks();
+
+    database::spawn(async move {
+        let Some(chain_client) = ChainClient::new(chain_rpc_url).ok_or_trace() else {
+            return;
+        };
+        let mut last_synced_block: Option<u64> = None;
+
+        loop {
+            if let Some(block_number) = chain_client.block_number().await.ok_or_trace() {
+                let is_due = match last_synced_block {
+                    Some(last_synced_block) => {
+                        block_number >= last_synced_block.saturating_add(sync_interval_blocks)
+                    }
+                    None => true,
+                };
+
+                if is_due
+                    && sync_key_generator_list(
+                        &context,
+                        &chain_client,
+                        &contract_address,
+                        block_number,
+                    )
+                    .await
+                    .ok_or_trace()
+                    .is_some()
+                {
+                    last_synced_block = Some(block_number);
+                }
+            }
+
+            tokio::time::sleep(REGISTRY_POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Replaces [`KeyGeneratorList`] with the registered set. A set that is empty
+/// or leaves out this node is refused, since following it would stop rounds
+/// (e.g. when the registry is misconfigured or read from the wrong chain);
+/// the list is then kept as is and the sync retried.
+async fn sync_key_generator_list(
+    context: &AppState,
+    chain_client: &ChainClient,
+    contract_address: &str,
+    block_number: u64,
+) -> Result<(), Error> {
+    let registered_key_generators =
+        fetch_registered_key_generators(chain_client, contract_address, Some(block_number)).await?;
+
+    if registered_key_generators.is_empty() {
+        return Err(Error::EmptyRegistry);
+    }
+    if !registered_key_generators
+        .iter()
+        .any(|key_generator| key_generator.address() == context.config().address())
+    {
+        return Err(Error::NotInRegistry);
+    }
+
+    let mut key_generator_list = KeyGeneratorList::get_mut()?;
+    let membership_change = key_generator_list.reconcile(registered_key_generators);
+    if membership_change.is_empty() {
+        return Ok(());
+    }
+    key_generator_list.update()?;
+
+    for key_generator in membership_change.added.iter() {
+        tracing::info!(
+            "Key generator joined through the registry - block: {} / address: {:?} / cluster_rpc_url: {:?}",
+            block_number,
+            key_generator.address().as_hex_string(),
+            key_generator.cluster_rpc_url()
+        );
+    }
+    for key_generator in membership_change.removed.iter() {
+        // A key generator that only changed its urls is still a member.
+        if !membership_change
+            .added
+            .iter()
+            .any(|added| added.address() == key_generator.address())
+        {
+            PeerLiveness::delete(key_generator.address())?;
+        }
+
+        tracing::info!(
+            "Key generator left through the registry - block: {} / address: {:?} / cluster_rpc_url: {:?}",
+            block_number,
+            key_generator.address().as_hex_string(),
+            key_generator.cluster_rpc_url()
+        );
+    }
+
+    Ok(())
+}
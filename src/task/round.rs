@@ -0,0 +1,153 @@
+use std::time::Instant;
+
+use radius_sdk::signature::Address;
+use skde::{
+    delay_encryption::solve_time_lock_puzzle,
+    key_aggregation::{aggregate_key, AggregatedKey as SkdeAggregatedKey},
+    key_generation::PartialKey as SkdePartialKey,
+};
+
+use crate::{
+    error::Error, metrics, rpc::cluster::sync_aggregated_key_signature, state::AppState, types::*,
+};
+
+/// Aggregates every partial key received for `key_id`, stores the result as
+/// the encryption key of `key_id` and announces it. Run by the leader's round
+/// driver; followers aggregate the leader's set through
+/// [`aggregate_participant_partial_keys`].
+///
+/// Returns the aggregated key and the addresses whose partial keys went into
+/// it.
+pub fn aggregate_partial_keys(
+    context: &AppState,
+    key_id: KeyId,
+) -> Result<(SkdeAggregatedKey, Vec<Address>), Error> {
+    let partial_key_address_list =
+        PartialKeyAddressList::get_or(key_id, PartialKeyAddressList::default)?;
+
+    let participant_addresses = partial_key_address_list.to_vec();
+    let partial_key_list = partial_key_address_list.get_partial_key_list(key_id)?;
+    if partial_key_list.is_empty() {
+        return Err(Error::NoPartialKey);
+    }
+
+    let skde_aggregated_key = aggregate(context, key_id, partial_key_list);
+    store_aggregated_key(context, key_id, &skde_aggregated_key)?;
+
+    Ok((skde_aggregated_key, participant_addresses))
+}
+
+/// Aggregates the partial keys of exactly `participant_addresses`, the set
+/// the leader aggregated, so that a follower that received other partial
+/// keys still ends up with the leader's key. Fails without storing anything
+/// when one of them is missing here or the result is not
+/// `leader_aggregated_key`.
+pub fn aggregate_participant_partial_keys(
+    context: &AppState,
+    key_id: KeyId,
+    participant_addresses: &[Address],
+    leader_aggregated_key: &SkdeAggregatedKey,
+) -> Result<SkdeAggregatedKey, Error> {
+    let partial_key_list = participant_addresses
+        .iter()
+        .map(|address| {
+            PartialKey::get(key_id, address)
+                .map(PartialKey::into_inner)
+                .map_err(|_| Error::MissingPartialKey(address.as_hex_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if partial_key_list.is_empty() {
+        return Err(Error::NoPartialKey);
+    }
+
+    let skde_aggregated_key = aggregate(context, key_id, partial_key_list);
+    if serde_json::to_value(&skde_aggregated_key).ok()
+        != serde_json::to_value(leader_aggregated_key).ok()
+    {
+        return Err(Error::AggregatedKeyMismatch);
+    }
+    store_aggregated_key(context, key_id, &skde_aggregated_key)?;
+
+    Ok(skde_aggregated_key)
+}
+
+fn aggregate(
+    context: &AppState,
+    key_id: KeyId,
+    partial_key_list: Vec<SkdePartialKey>,
+) -> SkdeAggregatedKey {
+    let started_at = Instant::now();
+    let skde_aggregated_key = aggregate_key(context.skde_params(), &partial_key_list);
+    metrics::record_aggregation(
+        key_id.as_u64(),
+        partial_key_list.len(),
+        started_at.elapsed(),
+    );
+
+    skde_aggregated_key
+}
+
+/// Stores `skde_aggregated_key` as the encryption key of `key_id`, announces
+/// it and signs it.
+fn store_aggregated_key(
+    context: &AppState,
+    key_id: KeyId,
+    skde_aggregated_key: &SkdeAggregatedKey,
+) -> Result<(), Error> {
+    let aggregated_key = AggregatedKey::new(skde_aggregated_key.clone());
+    aggregated_key.put(key_id)?;
+    LatestAggregatedKeyId::advance(key_id)?;
+
+    tracing::info!(
+        "Completed to generate encryption key - key id: {:?} / encryption key: {:?}",
+        key_id,
+        skde_aggregated_key.u
+    );
+
+    context.publish_key_event(KeyEvent::EncryptionKeyReady {
+        key_id,
+        encryption_key: skde_aggregated_key.u.clone(),
+    });
+
+    sync_aggregated_key_signature(context, key_id, skde_aggregated_key.u.clone())?;
+
+    Ok(())
+}
+
+/// Solves the time-lock puzzle of `skde_aggregated_key` on the blocking pool
+/// and stores the result as the decryption key of `key_id`.
+pub async fn solve_decryption_key(
+    context: &AppState,
+    key_id: KeyId,
+    skde_aggregated_key: SkdeAggregatedKey,
+    aggregated_at: Instant,
+) -> Result<(), Error> {
+    let skde_params = context.skde_params().clone();
+
+    let puzzle_job = context.start_puzzle_job();
+    let secure_key = tokio::task::spawn_blocking(move || {
+        solve_time_lock_puzzle(&skde_params, &skde_aggregated_key)
+    })
+    .await
+    .map_err(Error::JoinTask)?
+    .map_err(|error| Error::SolveTimeLockPuzzle(format!("{:?}", error)))?;
+    drop(puzzle_job);
+
+    let decryption_key = DecryptionKey::new(secure_key.sk);
+    decryption_key.put(key_id)?;
+    LatestDecryptionKeyId::advance(key_id)?;
+    metrics::record_time_to_decryption_key(aggregated_at.elapsed());
+
+    tracing::info!(
+        "Complete to get decryption key - key_id: {:?} / decryption key: {:?}",
+        key_id,
+        decryption_key
+    );
+
+    context.publish_key_event(KeyEvent::DecryptionKeyReady {
+        key_id,
+        decryption_key: decryption_key.as_string(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tokio::{
+    sync::Notify,
+    time::{sleep_until, Instant},
+};
+
+use crate::{
+    chain::ChainClient,
+    state::AppState,
+    task::TraceExt,
+    types::{BlockRange, KeyId, KeySlotClock, RoundSchedule},
+};
+
+const BLOCK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub type RoundFuture<'a> = Pin<Box<dyn Future<Output = RoundStart> + Send + 'a>>;
+
+/// Decides when the next key generation round starts.
+pub trait RoundScheduler: Send {
+    /// Waits until the next round starts.
+    fn next_round(&mut self) -> RoundFuture<'_>;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RoundStart {
+    /// The aggregation deadline of the round is derived from this.
+    pub started_at: Instant,
+    /// The blocks the round's key is for, under the `block_height` schedule.
+    pub block_range: Option<BlockRange>,
+    /// The slot the round was scheduled for, under the `slot` key id mode.
+    pub key_id: Option<KeyId>,
+}
+
+impl RoundStart {
+    pub fn at(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            block_range: None,
+            key_id: None,
+        }
+    }
+}
+
+/// Builds the scheduler selected by `round_schedule` in the config. Key ids
+/// derived from slots need rounds on slot boundaries, so the `slot` key id
+/// mode turns a fixed interval into a wall-clock schedule of the slots.
+pub fn round_scheduler(context: &AppState) -> Box<dyn RoundScheduler> {
+    let cycle = Duration::from_secs(context.config().partial_key_generation_cycle());
+    let key_slot_clock = context.config().key_slot_clock();
+
+    match (context.config().round_schedule(), key_slot_clock) {
+        (RoundSchedule::FixedInterval | RoundSchedule::WallClock, Some(key_slot_clock)) => {
+            Box::new(WallClockScheduler::for_key_slots(key_slot_clock))
+        }
+        (RoundSchedule::FixedInterval, None) => Box::new(FixedIntervalScheduler::new(cycle)),
+        (RoundSchedule::WallClock, None) => Box::new(WallClockScheduler::new(cycle, 0)),
+        (RoundSchedule::External, _) => {
+            Box::new(ExternalTriggerScheduler::new(context.round_trigger()))
+        }
+        (RoundSchedule::BlockHeight, _) => {
+            // Checked when the config is loaded.
+            let chain_rpc_url = context.config().chain_rpc_url().clone().unwrap_or_default();
+
+            Box::new(BlockHeightScheduler::new(
+                chain_rpc_url,
+                context.config().blocks_per_key(),
+                BLOCK_POLL_INTERVAL,
+            ))
+        }
+    }
+}
+
+/// Starts a round every `cycle`, counted from the previous round start so
+/// that slow rounds do not make the schedule drift.
+pub struct FixedIntervalScheduler {
+    cycle: Duration,
+    next_round_at: Instant,
+}
+
+impl FixedIntervalScheduler {
+    pub fn new(cycle: Duration) -> Self {
+        Self {
+            cycle,
+            next_round_at: Instant::now() + cycle,
+        }
+    }
+}
+
+impl RoundScheduler for FixedIntervalScheduler {
+    fn next_round(&mut self) -> RoundFuture<'_> {
+        Box::pin(async move {
+            let round_started_at = self.next_round_at;
+            sleep_until(round_started_at).await;
+
+            self.next_round_at = round_started_at + self.cycle;
+            RoundStart::at(round_started_at)
+        })
+    }
+}
+
+/// Starts a round at every `origin + k * cycle` (Unix seconds), so every
+/// node computes the same round boundaries from its own clock.
+pub struct WallClockScheduler {
+    cycle: Duration,
+    origin: Duration,
+    key_slot_clock: Option<KeySlotClock>,
+    last_boundary: Option<SystemTime>,
+}
+
+impl WallClockScheduler {
+    pub fn new(cycle: Duration, origin_secs: u64) -> Self {
+        Self {
+            cycle,
+            origin: Duration::from_secs(origin_secs),
+            key_slot_clock: None,
+            last_boundary: None,
+        }
+    }
+
+    /// Starts a round at the start of every slot of `key_slot_clock` and
+    /// tags it with the key id of that slot.
+    pub fn for_key_slots(key_slot_clock: KeySlotClock) -> Self {
+        Self {
+            cycle: Duration::from_secs(key_slot_clock.cycle),
+            origin: Duration::from_secs(key_slot_clock.genesis_time),
+            key_slot_clock: Some(key_slot_clock),
+            last_boundary: None,
+        }
+    }
+
+    /// Time left until the next boundary after `now`.
+    pub fn until_next_boundary(&self, now: SystemTime) -> Duration {
+        let since_epoch = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        if since_epoch < self.origin {
+            return self.origin - since_epoch;
+        }
+
+        let cycle = self.cycle.as_nanos().max(1);
+        let elapsed_in_cycle = (since_epoch - self.origin).as_nanos() % cycle;
+
+        Duration::from_nanos((cycle - elapsed_in_cycle) as u64)
+    }
+
+    /// The boundary the next round starts at, seen from `now`. Rounds sleep
+    /// on the monotonic clock, which the wall clock may lag behind (e.g.
+    /// while NTP slews it), so the boundary of the previous round can still
+    /// be ahead of `now`. The boundary after it is taken then, so no boundary
+    /// (and no slot key id) starts two rounds.
+    fn next_boundary(&self, now: SystemTime) -> SystemTime {
+        let boundary = now + self.until_next_boundary(now);
+
+        match self.last_boundary {
+            Some(last_boundary) if boundary <= last_boundary => last_boundary + self.cycle,
+            _ => boundary,
+        }
+    }
+}
+
+impl RoundScheduler for WallClockScheduler {
+    fn next_round(&mut self) -> RoundFuture<'_> {
+        Box::pin(async move {
+            let now = SystemTime::now();
+            let boundary = self.next_boundary(now);
+            // Taken from the boundary rather than from the clock once the
+            // round starts, which may already be past it.
+            let key_id = self
+                .key_slot_clock
+                .and_then(|key_slot_clock| key_slot_clock.key_id_at(boundary));
+
+            let round_started_at =
+                Instant::now() + boundary.duration_since(now).unwrap_or_default();
+            sleep_until(round_started_at).await;
+            self.last_boundary = Some(boundary);
+
+            RoundStart {
+                key_id,
+                ..RoundStart::at(round_started_at)
+            }
+        })
+    }
+}
+
+/// Starts a round whenever the trigger is notified, e.g. through the
+/// `trigger_round` internal RPC. A trigger that arrives while no round is
+/// awaited is kept until the next call.
+pub struct ExternalTriggerScheduler {
+    trigger: Arc<Notify>,
+}
+
+impl ExternalTriggerScheduler {
+    pub fn new(trigger: Arc<Notify>) -> Self {
+        Self { trigger }
+    }
+}
+
+impl RoundScheduler for ExternalTriggerScheduler {
+    fn next_round(&mut self) -> RoundFuture<'_> {
+        Box::pin(async move {
+            self.trigger.notified().await;
+
+            RoundStart::at(Instant::now())
+        })
+    }
+}
+
+/// Starts a round whenever the chain height enters the next range of
+/// `blocks_per_key` blocks. The range seen when the scheduler starts is
+/// already under way and does not get a round of its own. When the height
+/// skips ranges between two polls, every skipped range still gets its round,
+/// one per call and in order, so no key id is left without a key.
+pub struct BlockHeightScheduler {
+    chain_rpc_url: String,
+    chain_client: Option<ChainClient>,
+    blocks_per_key: u64,
+    poll_interval: Duration,
+    last_range_index: Option<u64>,
+    observed_range_index: Option<u64>,
+}
+
+impl BlockHeightScheduler {
+    pub fn new(chain_rpc_url: String, blocks_per_key: u64, poll_interval: Duration) -> Self {
+        Self {
+            chain_rpc_url,
+            chain_client: None,
+            blocks_per_key: blocks_per_key.max(1),
+            poll_interval,
+            last_range_index: None,
+            observed_range_index: None,
+        }
+    }
+
+    async fn block_number(&mut self) -> Option<u64> {
+        if self.chain_client.is_none() {
+            self.chain_client = ChainClient::new(self.chain_rpc_url.clone()).ok_or_trace();
+        }
+
+        self.chain_client
+            .as_ref()?
+            .block_number()
+            .await
+            .ok_or_trace()
+    }
+}
+
+impl RoundScheduler for BlockHeightScheduler {
+    fn next_round(&mut self) -> RoundFuture<'_> {
+        Box::pin(async move {
+            loop {
+                if let (Some(last_range_index), Some(observed_range_index)) =
+                    (self.last_range_index, self.observed_range_index)
+                {
+                    if observed_range_index > last_range_index {
+                        let range_index = last_range_index + 1;
+                        self.last_range_index = Some(range_index);
+
+                        return RoundStart {
+                            block_range: Some(BlockRange::from_index(
+                                range_index,
+                                self.blocks_per_key,
+                            )),
+                            ..RoundStart::at(Instant::now())
+                        };
+                    }
+                }
+
+                if let Some(block_number) = self.block_number().await {
+                    let range_index = block_number / self.blocks_per_key;
+
+                    if self.last_range_index.is_none() {
+                        self.last_range_index = Some(range_index);
+                    }
+                    self.observed_range_index = self.observed_range_index.max(Some(range_index));
+
+                    if self.observed_range_index > self.last_range_index {
+                        continue;
+                    }
+                }
+
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+}
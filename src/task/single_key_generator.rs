@@ -1,133 +1,187 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use radius_sdk::{
-    json_rpc::{
-        client::{Id, RpcClient},
-        server::RpcParameter,
-    },
-    signature::Address,
-};
-use skde::{
-    delay_encryption::solve_time_lock_puzzle,
-    key_aggregation::{aggregate_key, AggregatedKey as SkdeAggregatedKey},
-};
-use tokio::time::sleep;
+use radius_sdk::signature::Address;
+use skde::key_aggregation::AggregatedKey as SkdeAggregatedKey;
+use tokio::time::sleep_until;
+use tracing::Instrument;
 
 use crate::{
-    rpc::cluster::{RunGeneratePartialKey, SyncAggregatedKey},
+    database,
+    error::Error,
+    rpc::{
+        cluster::{RunGeneratePartialKey, SyncAggregatedKey},
+        spawn_multicast,
+    },
     state::AppState,
+    task::{
+        publisher::queue_key_commitment,
+        round::{aggregate_partial_keys, solve_decryption_key},
+        scheduler::{round_scheduler, RoundStart},
+        TraceExt,
+    },
     types::*,
 };
 
+/// Drives one round whenever the configured
+/// [`RoundScheduler`](crate::task::scheduler::RoundScheduler) says so. A
+/// round that fails is reported as [`KeyEvent::RoundFailed`] and counted, but
+/// never stops the loop.
+///
+/// Every wait goes through `tokio::time`, so tests can drive rounds under a
+/// paused clock together with a [`SimulatedNetwork`](crate::rpc::transport::SimulatedNetwork).
 pub fn run_single_key_generator(context: AppState) {
-    tokio::spawn(async move {
-        let partial_key_generation_cycle = context.config().partial_key_generation_cycle();
-        let partial_key_aggregation_cycle = context.config().partial_key_aggregation_cycle();
+    database::spawn(async move {
+        let mut round_scheduler = round_scheduler(&context);
 
         loop {
-            sleep(Duration::from_secs(partial_key_generation_cycle)).await;
-            let context = context.clone();
+            let round_start = round_scheduler.next_round().await;
 
-            let mut key_id = KeyId::get_mut().unwrap();
-            let current_key_id = key_id.clone();
-            key_id.increase_key_id();
-            key_id.update().unwrap();
-
-            run_generate_partial_key(current_key_id);
-
-            tokio::spawn(async move {
-                sleep(Duration::from_secs(partial_key_aggregation_cycle)).await;
-                let skde_params = context.skde_params().clone();
-
-                let partial_key_address_list =
-                    PartialKeyAddressList::get_or(current_key_id, PartialKeyAddressList::default)
-                        .unwrap();
-
-                let participant_addresses = partial_key_address_list.to_vec();
-                let partial_key_list = partial_key_address_list
-                    .get_partial_key_list(current_key_id)
-                    .unwrap();
-
-                let skde_aggregated_key = aggregate_key(&skde_params, &partial_key_list);
-
-                let aggregated_key = AggregatedKey::new(skde_aggregated_key.clone());
-                aggregated_key.put(current_key_id).unwrap();
-
-                tracing::info!(
-                    "Completed to generate encryption key - key id: {:?} / encryption key: {:?}",
-                    current_key_id,
-                    skde_aggregated_key.u
-                );
-
-                sync_aggregated_key(
-                    current_key_id,
-                    skde_aggregated_key.clone(),
-                    participant_addresses,
-                    context.config().signer().address(),
-                );
-
-                let secure_key =
-                    solve_time_lock_puzzle(&skde_params, &skde_aggregated_key).unwrap();
-                let decryption_key = DecryptionKey::new(secure_key.sk.clone());
-                decryption_key.put(current_key_id).unwrap();
-
-                tracing::info!(
-                    "Complete to get decryption key - key_id: {:?} / decryption key: {:?}",
-                    current_key_id,
-                    decryption_key
-                );
-            });
+            let Some(key_id) = next_key_id(&context, &round_start).ok_or_trace() else {
+                context.record_round_result(false);
+                continue;
+            };
+
+            let context = context.clone();
+            let round = async move {
+                match run_round(&context, key_id, round_start).await {
+                    Ok(()) => context.record_round_result(true),
+                    Err(error) => {
+                        context.record_round_result(false);
+
+                        tracing::error!(
+                            "Round failed - key_id: {:?} / error: {} / failed rounds: {}",
+                            key_id,
+                            error,
+                            context.round_stats().failed,
+                        );
+
+                        context.publish_key_event(KeyEvent::RoundFailed {
+                            key_id,
+                            reason: error.to_string(),
+                        });
+                    }
+                }
+            }
+            .instrument(tracing::info_span!("round", key_id = key_id.as_u64()));
+
+            database::spawn(round);
         }
     });
 }
 
-pub fn run_generate_partial_key(key_id: KeyId) {
-    let all_key_generator_rpc_url_list = KeyGeneratorList::get()
-        .unwrap()
-        .get_all_key_generator_rpc_url_list();
-
-    tokio::spawn(async move {
-        let parameter = RunGeneratePartialKey { key_id };
-
-        let rpc_client = RpcClient::new().unwrap();
-        rpc_client
-            .multicast(
-                all_key_generator_rpc_url_list,
-                RunGeneratePartialKey::method(),
-                &parameter,
-                Id::Null,
-            )
-            .await
-            .unwrap();
-    });
+/// Hands out the key id of the round starting now and moves the stored
+/// [`KeyId`] past it. In the `slot` key id mode the id is the slot the round
+/// was scheduled for (or else the current wall-clock slot) and a slot is
+/// never handed out twice.
+fn next_key_id(context: &AppState, round_start: &RoundStart) -> Result<KeyId, Error> {
+    let mut key_id = KeyId::get_mut()?;
+
+    let current_key_id = match context.config().key_slot_clock() {
+        Some(key_slot_clock) => {
+            let slot_key_id = round_start
+                .key_id
+                .or_else(|| key_slot_clock.current_key_id())
+                .ok_or(Error::BeforeGenesis)?;
+            if slot_key_id.as_u64() < key_id.as_u64() {
+                return Err(Error::KeyIdAlreadyUsed);
+            }
+
+            slot_key_id
+        }
+        None => *key_id,
+    };
+
+    *key_id = current_key_id;
+    key_id.increase_key_id();
+    key_id.update()?;
+
+    Ok(current_key_id)
+}
+
+async fn run_round(
+    context: &AppState,
+    key_id: KeyId,
+    round_start: RoundStart,
+) -> Result<(), Error> {
+    if let Some(block_range) = round_start.block_range {
+        block_range.put(key_id)?;
+    }
+
+    let expected_participant_count = run_generate_partial_key(context, key_id)?;
+
+    // Aggregate at a fixed offset from the round start rather than from now,
+    // so that nodes sharing a schedule agree on the deadline.
+    let partial_key_aggregation_cycle =
+        Duration::from_secs(context.config().partial_key_aggregation_cycle());
+    sleep_until(round_start.started_at + partial_key_aggregation_cycle).await;
+
+    let (skde_aggregated_key, participant_addresses) = aggregate_partial_keys(context, key_id)?;
+    let aggregated_at = Instant::now();
+
+    if participant_addresses.len() < expected_participant_count {
+        tracing::warn!(
+            "Aggregated fewer partial keys than expected - key_id: {:?} / expected: {} / received: {}",
+            key_id,
+            expected_participant_count,
+            participant_addresses.len()
+        );
+    }
+
+    if context.config().key_publisher() != KeyPublisherKind::None {
+        queue_key_commitment(
+            key_id,
+            &skde_aggregated_key.u,
+            participant_addresses.clone(),
+        )?;
+    }
+
+    sync_aggregated_key(
+        context,
+        key_id,
+        skde_aggregated_key.clone(),
+        participant_addresses,
+        round_start.block_range,
+    )?;
+
+    solve_decryption_key(context, key_id, skde_aggregated_key, aggregated_at).await
+}
+
+/// Asks every live key generator, this node included, for a partial key and
+/// returns how many were asked.
+pub fn run_generate_partial_key(context: &AppState, key_id: KeyId) -> Result<usize, Error> {
+    let alive_key_generator_rpc_url_list = KeyGeneratorList::get()?
+        .get_all_alive_key_generator_rpc_url_list(context.config().address());
+    let expected_participant_count = alive_key_generator_rpc_url_list.len();
+
+    spawn_multicast(
+        context,
+        alive_key_generator_rpc_url_list,
+        RunGeneratePartialKey { key_id },
+    );
+
+    Ok(expected_participant_count)
 }
 
 pub fn sync_aggregated_key(
+    context: &AppState,
     key_id: KeyId,
     aggregated_key: SkdeAggregatedKey,
     participant_addresses: Vec<Address>,
-    my_address: &Address,
-) {
-    let other_key_generator_rpc_url_list = KeyGeneratorList::get()
-        .unwrap()
-        .get_other_key_generator_rpc_url_list(my_address);
-
-    tokio::spawn(async move {
-        let parameter = SyncAggregatedKey {
+    block_range: Option<BlockRange>,
+) -> Result<(), Error> {
+    let other_key_generator_rpc_url_list = KeyGeneratorList::get()?
+        .get_other_alive_key_generator_rpc_url_list(context.config().address());
+
+    spawn_multicast(
+        context,
+        other_key_generator_rpc_url_list,
+        SyncAggregatedKey {
             key_id,
             aggregated_key,
             participant_addresses,
-        };
-
-        let rpc_client = RpcClient::new().unwrap();
-        rpc_client
-            .multicast(
-                other_key_generator_rpc_url_list,
-                SyncAggregatedKey::method(),
-                &parameter,
-                Id::Null,
-            )
-            .await
-            .unwrap();
-    });
+            block_range,
+        },
+    );
+
+    Ok(())
 }
@@ -0,0 +1,157 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use radius_sdk::signature::{Address, Signature};
+use serde::Serialize;
+
+use crate::{database, error::Error, state::AppState, task::TraceExt, types::*};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const DELIVERY_TICK: Duration = Duration::from_secs(1);
+const MAX_DELIVERY_ATTEMPTS: u32 = 10;
+const MAX_BACKOFF_SECS: u64 = 300;
+
+/// Body POSTed to every webhook URL. `signature` is produced by the node's
+/// signer over `key_event`, so receivers can check it against `signer`.
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    key_event: &'a KeyEvent,
+    signer: &'a Address,
+    signature: Signature,
+}
+
+/// Queues a delivery of `key_event` to every configured webhook URL. Called
+/// before the event is broadcast, so an event is persisted even if the
+/// dispatcher falls behind or the node stops before delivering it.
+pub fn queue_webhook_deliveries(context: &AppState, key_event: &KeyEvent) {
+    let webhook_urls = context.config().webhook_urls();
+    if webhook_urls.is_empty() {
+        return;
+    }
+
+    WebhookQueue::apply(|webhook_queue| {
+        for url in webhook_urls.iter() {
+            webhook_queue.push(url.clone(), key_event.clone(), now());
+        }
+    })
+    .ok_or_trace();
+}
+
+/// Delivers the queued [`KeyEvent`]s to the configured webhook URLs, retrying
+/// with exponential backoff until they are acknowledged with a 2xx status or
+/// run out of attempts.
+///
+/// Every URL is served by a task of its own, so an endpoint that is down or
+/// slow to answer does not hold up the deliveries to the others. Deliveries
+/// still queued for a URL that was removed from the config are delivered as
+/// well.
+pub fn run_webhook_dispatcher(context: AppState) {
+    let mut webhook_urls = context.config().webhook_urls().clone();
+    if let Ok(webhook_queue) = WebhookQueue::get() {
+        for url in webhook_queue.urls() {
+            if !webhook_urls.contains(&url) {
+                webhook_urls.push(url);
+            }
+        }
+    }
+    if webhook_urls.is_empty() {
+        return;
+    }
+
+    let Some(http_client) = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+        .map_err(Error::Webhook)
+        .ok_or_trace()
+    else {
+        return;
+    };
+
+    for url in webhook_urls {
+        let context = context.clone();
+        let http_client = http_client.clone();
+
+        database::spawn(async move {
+            let mut interval = tokio::time::interval(DELIVERY_TICK);
+
+            loop {
+                interval.tick().await;
+                deliver_due(&context, &http_client, &url).await;
+            }
+        });
+    }
+}
+
+/// Delivers the due deliveries to `url`, oldest first.
+async fn deliver_due(context: &AppState, http_client: &reqwest::Client, url: &str) {
+    let due_deliveries = match WebhookQueue::get() {
+        Ok(webhook_queue) => webhook_queue.due_deliveries(url, now()),
+        Err(_) => return,
+    };
+
+    for delivery in due_deliveries {
+        let result = deliver(context, http_client, &delivery).await;
+
+        WebhookQueue::apply(|webhook_queue| match &result {
+            Ok(()) => webhook_queue.remove(delivery.id),
+            Err(error) => {
+                let backoff = 2_u64
+                    .saturating_pow(delivery.attempts)
+                    .min(MAX_BACKOFF_SECS);
+
+                let attempts = webhook_queue
+                    .reschedule(delivery.id, now() + backoff)
+                    .map(|delivery| delivery.attempts)
+                    .unwrap_or_default();
+
+                tracing::warn!(
+                    "Failed to deliver webhook - url: {:?} / key_id: {:?} / attempts: {} / error: {}",
+                    delivery.url,
+                    delivery.key_event.key_id(),
+                    attempts,
+                    error,
+                );
+
+                if attempts >= MAX_DELIVERY_ATTEMPTS {
+                    tracing::error!(
+                        "Dropped webhook after {} attempts - url: {:?} / key_id: {:?}",
+                        attempts,
+                        delivery.url,
+                        delivery.key_event.key_id(),
+                    );
+                    webhook_queue.remove(delivery.id);
+                }
+            }
+        })
+        .ok_or_trace();
+    }
+}
+
+async fn deliver(
+    context: &AppState,
+    http_client: &reqwest::Client,
+    delivery: &WebhookDelivery,
+) -> Result<(), Error> {
+    let signer = context.config().signer();
+    let payload = WebhookPayload {
+        key_event: &delivery.key_event,
+        signer: signer.address(),
+        signature: signer.sign_message(&delivery.key_event)?,
+    };
+
+    http_client
+        .post(&delivery.url)
+        .json(&payload)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(Error::Webhook)?;
+
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
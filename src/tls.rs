@@ -0,0 +1,131 @@
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+
+use radius_sdk::signature::Address;
+use rustls::{
+    crypto::{ring, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+    server::WebPkiClientVerifier,
+    RootCertStore, ServerConfig,
+};
+use tokio_rustls::TlsAcceptor;
+
+use crate::types::{ExternalTlsFiles, TlsFiles};
+
+/// Domain under which cluster certificates name their key generator.
+pub const ADDRESS_NAME_DOMAIN: &str = "key-generator.radius";
+
+/// DNS name a cluster certificate must carry (as a subject alternative name)
+/// to be bound to `address`, e.g.
+/// `f39fd6e51aad88f6f4ce6ab8827279cfffb92266.key-generator.radius`.
+pub fn address_server_name(address: &Address) -> String {
+    let address = address.as_hex_string().to_ascii_lowercase();
+
+    format!(
+        "{}.{}",
+        address.trim_start_matches("0x"),
+        ADDRESS_NAME_DOMAIN
+    )
+}
+
+/// The first of `addresses` that `certificate` is bound to.
+pub fn bound_address<'a>(
+    certificate: &CertificateDer<'_>,
+    addresses: impl IntoIterator<Item = &'a Address>,
+) -> Option<&'a Address> {
+    let end_entity = webpki::EndEntityCert::try_from(certificate).ok()?;
+
+    addresses.into_iter().find(|address| {
+        let server_name = address_server_name(address);
+
+        ServerName::try_from(server_name.as_str())
+            .map(|server_name| {
+                end_entity
+                    .verify_is_valid_for_subject_name(&server_name)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Server side of the cluster port: requires a client certificate issued by
+/// the cluster CA.
+pub fn mutual_tls_acceptor(tls_files: &TlsFiles) -> Result<TlsAcceptor, TlsError> {
+    let provider = provider();
+    let roots = Arc::new(load_roots(&tls_files.ca_path)?);
+    let client_verifier = WebPkiClientVerifier::builder_with_provider(roots, provider.clone())
+        .build()
+        .map_err(|error| TlsError::Verifier(error.to_string()))?;
+
+    let server_config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(TlsError::Rustls)?
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(
+            load_certificates(&tls_files.cert_path)?,
+            load_private_key(&tls_files.key_path)?,
+        )
+        .map_err(TlsError::Rustls)?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// Server side without client authentication, for the external port.
+pub fn tls_acceptor(tls_files: &ExternalTlsFiles) -> Result<TlsAcceptor, TlsError> {
+    let server_config = ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(TlsError::Rustls)?
+        .with_no_client_auth()
+        .with_single_cert(
+            load_certificates(&tls_files.cert_path)?,
+            load_private_key(&tls_files.key_path)?,
+        )
+        .map_err(TlsError::Rustls)?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+fn load_roots(path: &Path) -> Result<RootCertStore, TlsError> {
+    let mut roots = RootCertStore::empty();
+    for certificate in load_certificates(path)? {
+        roots.add(certificate).map_err(TlsError::Rustls)?;
+    }
+
+    Ok(roots)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let mut reader = BufReader::new(File::open(path).map_err(TlsError::Io)?);
+
+    rustls_pemfile::certs(&mut reader)
+        .collect::<Result<_, _>>()
+        .map_err(TlsError::Io)
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, TlsError> {
+    let mut reader = BufReader::new(File::open(path).map_err(TlsError::Io)?);
+
+    rustls_pemfile::private_key(&mut reader)
+        .map_err(TlsError::Io)?
+        .ok_or(TlsError::MissingPrivateKey)
+}
+
+#[derive(Debug)]
+pub enum TlsError {
+    Io(std::io::Error),
+    MissingPrivateKey,
+    Rustls(rustls::Error),
+    Verifier(String),
+    Http(reqwest::Error),
+}
+
+impl std::fmt::Display for TlsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for TlsError {}
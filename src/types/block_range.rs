@@ -0,0 +1,30 @@
+use crate::{
+    database::model,
+    types::{prelude::*, KeyId},
+};
+
+/// Inclusive range of chain blocks a key id was generated for when rounds
+/// follow the block height.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockRange {
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+model!(BlockRange, key(key_id: KeyId));
+
+impl BlockRange {
+    /// Range number `index` when every key covers `blocks_per_key` blocks.
+    pub fn from_index(index: u64, blocks_per_key: u64) -> Self {
+        let from_block = index.saturating_mul(blocks_per_key);
+
+        Self {
+            from_block,
+            to_block: from_block.saturating_add(blocks_per_key.saturating_sub(1)),
+        }
+    }
+
+    pub fn contains(&self, block_number: u64) -> bool {
+        (self.from_block..=self.to_block).contains(&block_number)
+    }
+}
@@ -4,9 +4,14 @@ use clap::Parser;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    config_path::ConfigPath, DEFAULT_CHAIN_TYPE, DEFAULT_CLUSTER_RPC_URL, DEFAULT_EXTERNAL_RPC_URL,
-    DEFAULT_INTERNAL_RPC_URL, DEFAULT_PARTIAL_KEY_AGGREGATION_CYCLE,
+    config_path::ConfigPath, DEFAULT_BLOCKS_PER_KEY, DEFAULT_CHAIN_TYPE, DEFAULT_CLUSTER_RPC_URL,
+    DEFAULT_CLUSTER_UPSTREAM_PORT, DEFAULT_EXTERNAL_MAX_BODY_SIZE,
+    DEFAULT_EXTERNAL_MAX_CONCURRENT_REQUESTS, DEFAULT_EXTERNAL_MAX_CONNECTIONS_PER_IP,
+    DEFAULT_EXTERNAL_RPC_URL, DEFAULT_EXTERNAL_UPSTREAM_PORT, DEFAULT_HEALTH_CHECK_URL,
+    DEFAULT_INTERNAL_RPC_URL, DEFAULT_KEY_ID_MODE, DEFAULT_KEY_PUBLISHER, DEFAULT_LOG_FORMAT,
+    DEFAULT_LOG_LEVEL, DEFAULT_LOG_ROTATION, DEFAULT_PARTIAL_KEY_AGGREGATION_CYCLE,
     DEFAULT_PARTIAL_KEY_GENERATION_CYCLE, DEFAULT_RADIUS_FOUNDATION_ADDRESS,
+    DEFAULT_REGISTRY_SYNC_INTERVAL_BLOCKS, DEFAULT_ROUND_SCHEDULE,
 };
 
 #[derive(Debug, Deserialize, Parser, Serialize)]
@@ -27,6 +32,10 @@ pub struct ConfigOption {
     #[clap(long = "cluster-rpc-url")]
     pub cluster_rpc_url: Option<String>,
 
+    #[doc = "Set the health check url (serves plain HTTP /health, /ready and /metrics on its host)"]
+    #[clap(long = "health-check-url")]
+    pub health_check_url: Option<String>,
+
     #[doc = "Set the seed cluster rpc url"]
     #[clap(long = "seed-cluster-rpc-url")]
     pub seed_cluster_rpc_url: Option<String>,
@@ -46,6 +55,130 @@ pub struct ConfigOption {
     #[doc = "Set partial key aggregation cycle"]
     #[clap(long = "partial-key-aggregation-cycle")]
     pub partial_key_aggregation_cycle: Option<u64>,
+
+    #[doc = "Set when rounds start (\"fixed_interval\", \"wall_clock\", \"external\" or \"block_height\")"]
+    #[clap(long = "round-schedule")]
+    pub round_schedule: Option<String>,
+
+    #[doc = "Set where key ids come from (\"counter\" or \"slot\")"]
+    #[clap(long = "key-id-mode")]
+    pub key_id_mode: Option<String>,
+
+    #[doc = "Set the unix timestamp at which key id 0 starts (required for the \"slot\" key id mode)"]
+    #[clap(long = "genesis-time")]
+    pub genesis_time: Option<u64>,
+
+    #[doc = "Set the chain rpc url to follow the block height from"]
+    #[clap(long = "chain-rpc-url")]
+    pub chain_rpc_url: Option<String>,
+
+    #[doc = "Set the number of blocks covered by one key under the \"block_height\" round schedule"]
+    #[clap(long = "blocks-per-key")]
+    pub blocks_per_key: Option<u64>,
+
+    #[doc = "Set the key generator registry contract to read the cluster membership from"]
+    #[clap(long = "registry-contract-address")]
+    pub registry_contract_address: Option<String>,
+
+    #[doc = "Set how many blocks pass between two reads of the key generator registry"]
+    #[clap(long = "registry-sync-interval-blocks")]
+    pub registry_sync_interval_blocks: Option<u64>,
+
+    #[doc = "Set where aggregated key commitments are published (\"none\", \"ethereum\", \"http\" or \"file\")"]
+    #[clap(long = "key-publisher")]
+    pub key_publisher: Option<String>,
+
+    #[doc = "Set the key commitment contract for the \"ethereum\" key publisher"]
+    #[clap(long = "key-publisher-contract-address")]
+    pub key_publisher_contract_address: Option<String>,
+
+    #[doc = "Set the endpoint for the \"http\" key publisher"]
+    #[clap(long = "key-publisher-url")]
+    pub key_publisher_url: Option<String>,
+
+    #[doc = "Set the file for the \"file\" key publisher"]
+    #[clap(long = "key-publisher-file-path")]
+    pub key_publisher_file_path: Option<PathBuf>,
+
+    #[doc = "Set the webhook urls to deliver key events to"]
+    #[clap(long = "webhook-urls", value_delimiter = ',')]
+    pub webhook_urls: Option<Vec<String>>,
+
+    #[doc = "Set whether to serve the encrypt / decrypt external rpc methods"]
+    #[clap(long = "enable-encryption-rpc")]
+    pub enable_encryption_rpc: Option<bool>,
+
+    #[doc = "Set the sustained external rpc requests per second allowed per IP address (unlimited if not set)"]
+    #[clap(long = "external-rate-limit-per-second")]
+    pub external_rate_limit_per_second: Option<u32>,
+
+    #[doc = "Set the external rpc requests an IP address may send in a burst (defaults to the per second limit)"]
+    #[clap(long = "external-rate-limit-burst")]
+    pub external_rate_limit_burst: Option<u32>,
+
+    #[doc = "Set the largest accepted external rpc request body in bytes"]
+    #[clap(long = "external-max-body-size")]
+    pub external_max_body_size: Option<usize>,
+
+    #[doc = "Set the number of external rpc requests handled at the same time"]
+    #[clap(long = "external-max-concurrent-requests")]
+    pub external_max_concurrent_requests: Option<usize>,
+
+    #[doc = "Set the number of connections a single IP address may keep open to the external rpc server"]
+    #[clap(long = "external-max-connections-per-ip")]
+    pub external_max_connections_per_ip: Option<usize>,
+
+    #[doc = "Set the api keys accepted by the external rpc server (open to everyone if not set)"]
+    #[clap(long = "external-api-keys", value_delimiter = ',')]
+    pub external_api_keys: Option<Vec<String>>,
+
+    #[doc = "Set the PEM certificate chain bound to this key generator's address (enables TLS on the cluster port)"]
+    #[clap(long = "cluster-tls-cert-path")]
+    pub cluster_tls_cert_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM private key of the cluster certificate"]
+    #[clap(long = "cluster-tls-key-path")]
+    pub cluster_tls_key_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM certificate of the CA that issues cluster certificates"]
+    #[clap(long = "cluster-tls-ca-path")]
+    pub cluster_tls_ca_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM certificate chain the external rpc server serves TLS with (enables TLS on the external port)"]
+    #[clap(long = "external-tls-cert-path")]
+    pub external_tls_cert_path: Option<PathBuf>,
+
+    #[doc = "Set the PEM private key of the external certificate"]
+    #[clap(long = "external-tls-key-path")]
+    pub external_tls_key_path: Option<PathBuf>,
+
+    #[doc = "Set the loopback port the external rpc server listens on behind its gateway"]
+    #[clap(long = "external-upstream-port")]
+    pub external_upstream_port: Option<u16>,
+
+    #[doc = "Set the loopback port the cluster rpc server listens on behind its TLS gateway"]
+    #[clap(long = "cluster-upstream-port")]
+    pub cluster_upstream_port: Option<u16>,
+
+    #[doc = "Set the log level or filter directives (e.g. \"info,distributed_key_generation::rpc=debug\")"]
+    #[clap(long = "log-level")]
+    pub log_level: Option<String>,
+
+    #[doc = "Set the log format (\"pretty\" or \"json\")"]
+    #[clap(long = "log-format")]
+    pub log_format: Option<String>,
+
+    #[doc = "Set the log file path (logs are written to stdout if not set)"]
+    #[clap(long = "log-file-path")]
+    pub log_file_path: Option<PathBuf>,
+
+    #[doc = "Set the log file rotation (\"minutely\", \"hourly\", \"daily\" or \"never\")"]
+    #[clap(long = "log-rotation")]
+    pub log_rotation: Option<String>,
+
+    #[doc = "Set whether to print secret material such as decryption keys in logs"]
+    #[clap(long = "log-secrets")]
+    pub log_secrets: Option<bool>,
 }
 
 impl Default for ConfigOption {
@@ -56,11 +189,43 @@ impl Default for ConfigOption {
             external_rpc_url: Some(DEFAULT_EXTERNAL_RPC_URL.into()),
             internal_rpc_url: Some(DEFAULT_INTERNAL_RPC_URL.into()),
             cluster_rpc_url: Some(DEFAULT_CLUSTER_RPC_URL.into()),
+            health_check_url: Some(DEFAULT_HEALTH_CHECK_URL.into()),
             seed_cluster_rpc_url: None,
             radius_foundation_address: Some(DEFAULT_RADIUS_FOUNDATION_ADDRESS.into()),
             chain_type: Some(DEFAULT_CHAIN_TYPE.into()),
             partial_key_generation_cycle: Some(DEFAULT_PARTIAL_KEY_GENERATION_CYCLE),
             partial_key_aggregation_cycle: Some(DEFAULT_PARTIAL_KEY_AGGREGATION_CYCLE),
+            round_schedule: Some(DEFAULT_ROUND_SCHEDULE.into()),
+            key_id_mode: Some(DEFAULT_KEY_ID_MODE.into()),
+            genesis_time: None,
+            chain_rpc_url: None,
+            blocks_per_key: Some(DEFAULT_BLOCKS_PER_KEY),
+            registry_contract_address: None,
+            registry_sync_interval_blocks: Some(DEFAULT_REGISTRY_SYNC_INTERVAL_BLOCKS),
+            key_publisher: Some(DEFAULT_KEY_PUBLISHER.into()),
+            key_publisher_contract_address: None,
+            key_publisher_url: None,
+            key_publisher_file_path: None,
+            webhook_urls: None,
+            enable_encryption_rpc: Some(false),
+            external_rate_limit_per_second: None,
+            external_rate_limit_burst: None,
+            external_max_body_size: Some(DEFAULT_EXTERNAL_MAX_BODY_SIZE),
+            external_max_concurrent_requests: Some(DEFAULT_EXTERNAL_MAX_CONCURRENT_REQUESTS),
+            external_max_connections_per_ip: Some(DEFAULT_EXTERNAL_MAX_CONNECTIONS_PER_IP),
+            external_api_keys: None,
+            cluster_tls_cert_path: None,
+            cluster_tls_key_path: None,
+            cluster_tls_ca_path: None,
+            external_tls_cert_path: None,
+            external_tls_key_path: None,
+            external_upstream_port: Some(DEFAULT_EXTERNAL_UPSTREAM_PORT),
+            cluster_upstream_port: Some(DEFAULT_CLUSTER_UPSTREAM_PORT),
+            log_level: Some(DEFAULT_LOG_LEVEL.into()),
+            log_format: Some(DEFAULT_LOG_FORMAT.into()),
+            log_file_path: None,
+            log_rotation: Some(DEFAULT_LOG_ROTATION.into()),
+            log_secrets: Some(false),
         }
     }
 }
@@ -78,6 +243,9 @@ impl ConfigOption {
         set_toml_comment(&mut toml_string, "Set cluster rpc url");
         set_toml_name_value(&mut toml_string, "cluster_rpc_url", &self.cluster_rpc_url);
 
+        set_toml_comment(&mut toml_string, "Set health check url");
+        set_toml_name_value(&mut toml_string, "health_check_url", &self.health_check_url);
+
         set_toml_comment(&mut toml_string, "Set seed cluster rpc url");
         set_toml_name_value(
             &mut toml_string,
@@ -112,6 +280,256 @@ impl ConfigOption {
             &self.partial_key_aggregation_cycle,
         );
 
+        set_toml_comment(
+            &mut toml_string,
+            "Set when rounds start (\"fixed_interval\", \"wall_clock\", \"external\" or \"block_height\")",
+        );
+        set_toml_name_value(&mut toml_string, "round_schedule", &self.round_schedule);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set where key ids come from (\"counter\" or \"slot\")",
+        );
+        set_toml_name_value(&mut toml_string, "key_id_mode", &self.key_id_mode);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the unix timestamp at which key id 0 starts (required for the \"slot\" key id mode)",
+        );
+        set_toml_name_value(&mut toml_string, "genesis_time", &self.genesis_time);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the chain rpc url to follow the block height from",
+        );
+        set_toml_name_value(&mut toml_string, "chain_rpc_url", &self.chain_rpc_url);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the number of blocks covered by one key under the \"block_height\" round schedule",
+        );
+        set_toml_name_value(&mut toml_string, "blocks_per_key", &self.blocks_per_key);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the key generator registry contract to read the cluster membership from",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "registry_contract_address",
+            &self.registry_contract_address,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set how many blocks pass between two reads of the key generator registry",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "registry_sync_interval_blocks",
+            &self.registry_sync_interval_blocks,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set where aggregated key commitments are published (\"none\", \"ethereum\", \"http\" or \"file\")",
+        );
+        set_toml_name_value(&mut toml_string, "key_publisher", &self.key_publisher);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the key commitment contract for the \"ethereum\" key publisher",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "key_publisher_contract_address",
+            &self.key_publisher_contract_address,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the endpoint for the \"http\" key publisher",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "key_publisher_url",
+            &self.key_publisher_url,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the file for the \"file\" key publisher",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "key_publisher_file_path",
+            &self.key_publisher_file_path,
+        );
+
+        set_toml_comment(&mut toml_string, "Set the webhook urls to deliver key events to");
+        set_toml_name_value(&mut toml_string, "webhook_urls", &self.webhook_urls);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set whether to serve the encrypt / decrypt external rpc methods",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "enable_encryption_rpc",
+            &self.enable_encryption_rpc,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the sustained external rpc requests per second allowed per IP address (unlimited if not set)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_rate_limit_per_second",
+            &self.external_rate_limit_per_second,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the external rpc requests an IP address may send in a burst (defaults to the per second limit)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_rate_limit_burst",
+            &self.external_rate_limit_burst,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the largest accepted external rpc request body in bytes",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_max_body_size",
+            &self.external_max_body_size,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the number of external rpc requests handled at the same time",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_max_concurrent_requests",
+            &self.external_max_concurrent_requests,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the number of connections a single IP address may keep open to the external rpc server",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_max_connections_per_ip",
+            &self.external_max_connections_per_ip,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the api keys accepted by the external rpc server (open to everyone if not set)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_api_keys",
+            &self.external_api_keys,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the PEM certificate chain bound to this key generator's address (enables TLS on the cluster port)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "cluster_tls_cert_path",
+            &self.cluster_tls_cert_path,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the PEM private key of the cluster certificate",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "cluster_tls_key_path",
+            &self.cluster_tls_key_path,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the PEM certificate of the CA that issues cluster certificates",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "cluster_tls_ca_path",
+            &self.cluster_tls_ca_path,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the PEM certificate chain the external rpc server serves TLS with (enables TLS on the external port)",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_tls_cert_path",
+            &self.external_tls_cert_path,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the PEM private key of the external certificate",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_tls_key_path",
+            &self.external_tls_key_path,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the loopback port the external rpc server listens on behind its gateway",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "external_upstream_port",
+            &self.external_upstream_port,
+        );
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set the loopback port the cluster rpc server listens on behind its TLS gateway",
+        );
+        set_toml_name_value(
+            &mut toml_string,
+            "cluster_upstream_port",
+            &self.cluster_upstream_port,
+        );
+
+        set_toml_comment(&mut toml_string, "Set log level or filter directives");
+        set_toml_name_value(&mut toml_string, "log_level", &self.log_level);
+
+        set_toml_comment(&mut toml_string, "Set log format (\"pretty\" or \"json\")");
+        set_toml_name_value(&mut toml_string, "log_format", &self.log_format);
+
+        set_toml_comment(&mut toml_string, "Set log file path");
+        set_toml_name_value(&mut toml_string, "log_file_path", &self.log_file_path);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set log file rotation (\"minutely\", \"hourly\", \"daily\" or \"never\")",
+        );
+        set_toml_name_value(&mut toml_string, "log_rotation", &self.log_rotation);
+
+        set_toml_comment(
+            &mut toml_string,
+            "Set whether to print secret material such as decryption keys in logs",
+        );
+        set_toml_name_value(&mut toml_string, "log_secrets", &self.log_secrets);
+
         toml_string
     }
 
@@ -132,6 +550,10 @@ impl ConfigOption {
             self.cluster_rpc_url.clone_from(&other.cluster_rpc_url);
         }
 
+        if other.health_check_url.is_some() {
+            self.health_check_url.clone_from(&other.health_check_url);
+        }
+
         if other.seed_cluster_rpc_url.is_some() {
             self.seed_cluster_rpc_url
                 .clone_from(&other.seed_cluster_rpc_url);
@@ -147,8 +569,8 @@ impl ConfigOption {
         }
 
         if other.partial_key_generation_cycle.is_some() {
-            self.partial_key_aggregation_cycle
-                .clone_from(&other.partial_key_aggregation_cycle);
+            self.partial_key_generation_cycle
+                .clone_from(&other.partial_key_generation_cycle);
         }
 
         if other.partial_key_aggregation_cycle.is_some() {
@@ -156,6 +578,147 @@ impl ConfigOption {
                 .clone_from(&other.partial_key_aggregation_cycle);
         }
 
+        if other.round_schedule.is_some() {
+            self.round_schedule.clone_from(&other.round_schedule);
+        }
+
+        if other.key_id_mode.is_some() {
+            self.key_id_mode.clone_from(&other.key_id_mode);
+        }
+
+        if other.genesis_time.is_some() {
+            self.genesis_time.clone_from(&other.genesis_time);
+        }
+
+        if other.chain_rpc_url.is_some() {
+            self.chain_rpc_url.clone_from(&other.chain_rpc_url);
+        }
+
+        if other.blocks_per_key.is_some() {
+            self.blocks_per_key.clone_from(&other.blocks_per_key);
+        }
+
+        if other.registry_contract_address.is_some() {
+            self.registry_contract_address
+                .clone_from(&other.registry_contract_address);
+        }
+
+        if other.registry_sync_interval_blocks.is_some() {
+            self.registry_sync_interval_blocks
+                .clone_from(&other.registry_sync_interval_blocks);
+        }
+
+        if other.key_publisher.is_some() {
+            self.key_publisher.clone_from(&other.key_publisher);
+        }
+
+        if other.key_publisher_contract_address.is_some() {
+            self.key_publisher_contract_address
+                .clone_from(&other.key_publisher_contract_address);
+        }
+
+        if other.key_publisher_url.is_some() {
+            self.key_publisher_url.clone_from(&other.key_publisher_url);
+        }
+
+        if other.key_publisher_file_path.is_some() {
+            self.key_publisher_file_path
+                .clone_from(&other.key_publisher_file_path);
+        }
+
+        if other.webhook_urls.is_some() {
+            self.webhook_urls.clone_from(&other.webhook_urls);
+        }
+
+        if other.enable_encryption_rpc.is_some() {
+            self.enable_encryption_rpc
+                .clone_from(&other.enable_encryption_rpc);
+        }
+
+        if other.external_rate_limit_per_second.is_some() {
+            self.external_rate_limit_per_second
+                .clone_from(&other.external_rate_limit_per_second);
+        }
+
+        if other.external_rate_limit_burst.is_some() {
+            self.external_rate_limit_burst
+                .clone_from(&other.external_rate_limit_burst);
+        }
+
+        if other.external_max_body_size.is_some() {
+            self.external_max_body_size
+                .clone_from(&other.external_max_body_size);
+        }
+
+        if other.external_max_concurrent_requests.is_some() {
+            self.external_max_concurrent_requests
+                .clone_from(&other.external_max_concurrent_requests);
+        }
+
+        if other.external_max_connections_per_ip.is_some() {
+            self.external_max_connections_per_ip
+                .clone_from(&other.external_max_connections_per_ip);
+        }
+
+        if other.external_api_keys.is_some() {
+            self.external_api_keys.clone_from(&other.external_api_keys);
+        }
+
+        if other.cluster_tls_cert_path.is_some() {
+            self.cluster_tls_cert_path
+                .clone_from(&other.cluster_tls_cert_path);
+        }
+
+        if other.cluster_tls_key_path.is_some() {
+            self.cluster_tls_key_path
+                .clone_from(&other.cluster_tls_key_path);
+        }
+
+        if other.cluster_tls_ca_path.is_some() {
+            self.cluster_tls_ca_path
+                .clone_from(&other.cluster_tls_ca_path);
+        }
+
+        if other.external_tls_cert_path.is_some() {
+            self.external_tls_cert_path
+                .clone_from(&other.external_tls_cert_path);
+        }
+
+        if other.external_tls_key_path.is_some() {
+            self.external_tls_key_path
+                .clone_from(&other.external_tls_key_path);
+        }
+
+        if other.external_upstream_port.is_some() {
+            self.external_upstream_port
+                .clone_from(&other.external_upstream_port);
+        }
+
+        if other.cluster_upstream_port.is_some() {
+            self.cluster_upstream_port
+                .clone_from(&other.cluster_upstream_port);
+        }
+
+        if other.log_level.is_some() {
+            self.log_level.clone_from(&other.log_level);
+        }
+
+        if other.log_format.is_some() {
+            self.log_format.clone_from(&other.log_format);
+        }
+
+        if other.log_file_path.is_some() {
+            self.log_file_path.clone_from(&other.log_file_path);
+        }
+
+        if other.log_rotation.is_some() {
+            self.log_rotation.clone_from(&other.log_rotation);
+        }
+
+        if other.log_secrets.is_some() {
+            self.log_secrets.clone_from(&other.log_secrets);
+        }
+
         self
     }
 }
@@ -5,7 +5,7 @@ use std::{
 
 use clap::Parser;
 use serde::{Deserialize, Serialize};
-use crate::util::clear_dir;
+use crate::{logger::Secret, util::clear_dir};
 
 use crate::types::{
     config::{config_option::ConfigOption, ConfigError},
@@ -64,7 +64,7 @@ impl ConfigPath {
         let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
         fs::write(signing_key_path, signing_key).map_err(ConfigError::CreatePrivateKeyFile)?;
 
-        tracing::info!("Created a sign key {:?}", signing_key);
+        tracing::info!("Created a sign key {:?}", Secret(signing_key));
         tracing::info!("Created a new config directory at {:?}", self.as_ref());
         Ok(())
     }
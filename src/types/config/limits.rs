@@ -0,0 +1,19 @@
+/// Limits enforced by the RPC gateway in front of an RPC server.
+#[derive(Clone, Debug)]
+pub struct RpcLimits {
+    /// Sustained requests per second allowed from a single IP address.
+    /// Unlimited if `None`.
+    pub rate_limit_per_second: Option<u32>,
+    /// Requests a single IP address may send in a burst on top of the
+    /// sustained rate.
+    pub rate_limit_burst: u32,
+    /// Largest accepted request body in bytes.
+    pub max_body_size: usize,
+    /// Requests handled at the same time across all clients.
+    pub max_concurrent_requests: usize,
+    /// Connections a single IP address may keep open at the same time.
+    pub max_connections_per_ip: usize,
+    /// Accepted `x-api-key` / `Authorization: Bearer` values. Every client
+    /// is accepted if empty.
+    pub api_keys: Vec<String>,
+}
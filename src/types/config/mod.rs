@@ -1,11 +1,23 @@
 mod config_option;
 mod config_path;
+mod limits;
+mod log;
+mod publisher;
+mod schedule;
+mod tls;
 
 use std::{fs, path::PathBuf};
 
 pub use config_option::*;
 pub use config_path::*;
+pub use limits::*;
+pub use log::*;
+pub use publisher::*;
 use radius_sdk::signature::{Address, ChainType, PrivateKeySigner};
+pub use schedule::*;
+pub use tls::*;
+
+use crate::types::KeySlotClock;
 
 pub const DEFAULT_HOME_PATH: &str = ".radius";
 pub const DATABASE_DIR_NAME: &str = "database";
@@ -15,12 +27,28 @@ pub const SIGNING_KEY: &str = "signing_key";
 const DEFAULT_EXTERNAL_RPC_URL: &str = "http://127.0.0.1:3000";
 const DEFAULT_INTERNAL_RPC_URL: &str = "http://127.0.0.1:4000";
 const DEFAULT_CLUSTER_RPC_URL: &str = "http://127.0.0.1:5000";
+const DEFAULT_HEALTH_CHECK_URL: &str = "http://127.0.0.1:6000";
+const DEFAULT_EXTERNAL_UPSTREAM_PORT: u16 = 3001;
+const DEFAULT_CLUSTER_UPSTREAM_PORT: u16 = 5001;
 
 const DEFAULT_RADIUS_FOUNDATION_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
 const DEFAULT_CHAIN_TYPE: &str = "ethereum";
 
 const DEFAULT_PARTIAL_KEY_GENERATION_CYCLE: u64 = 5;
 const DEFAULT_PARTIAL_KEY_AGGREGATION_CYCLE: u64 = 4;
+const DEFAULT_ROUND_SCHEDULE: &str = "fixed_interval";
+const DEFAULT_KEY_ID_MODE: &str = "counter";
+const DEFAULT_BLOCKS_PER_KEY: u64 = 1;
+const DEFAULT_REGISTRY_SYNC_INTERVAL_BLOCKS: u64 = 10;
+const DEFAULT_KEY_PUBLISHER: &str = "none";
+
+const DEFAULT_EXTERNAL_MAX_BODY_SIZE: usize = 1024 * 1024;
+const DEFAULT_EXTERNAL_MAX_CONCURRENT_REQUESTS: usize = 256;
+const DEFAULT_EXTERNAL_MAX_CONNECTIONS_PER_IP: usize = 64;
+
+const DEFAULT_LOG_LEVEL: &str = "info";
+const DEFAULT_LOG_FORMAT: &str = "pretty";
+const DEFAULT_LOG_ROTATION: &str = "daily";
 
 #[derive(Clone)]
 pub struct Config {
@@ -29,6 +57,7 @@ pub struct Config {
     external_rpc_url: String,
     internal_rpc_url: String,
     cluster_rpc_url: String,
+    health_check_url: Option<String>,
     seed_cluster_rpc_url: Option<String>,
 
     signer: PrivateKeySigner,
@@ -38,6 +67,32 @@ pub struct Config {
 
     partial_key_generation_cycle: u64,
     partial_key_aggregation_cycle: u64,
+    round_schedule: RoundSchedule,
+    key_id_mode: KeyIdMode,
+    genesis_time: Option<u64>,
+    chain_rpc_url: Option<String>,
+    blocks_per_key: u64,
+    registry_contract_address: Option<String>,
+    registry_sync_interval_blocks: u64,
+
+    key_publisher: KeyPublisherKind,
+    key_publisher_contract_address: Option<String>,
+    key_publisher_url: Option<String>,
+    key_publisher_file_path: Option<PathBuf>,
+
+    webhook_urls: Vec<String>,
+    enable_encryption_rpc: bool,
+    external_rpc_limits: RpcLimits,
+    cluster_tls: Option<TlsFiles>,
+    external_tls: Option<ExternalTlsFiles>,
+    external_upstream_port: u16,
+    cluster_upstream_port: u16,
+
+    log_level: String,
+    log_format: LogFormat,
+    log_file_path: Option<PathBuf>,
+    log_rotation: LogRotation,
+    log_secrets: bool,
 }
 
 impl Config {
@@ -64,6 +119,90 @@ impl Config {
 
         let chain_type = merged_config_option.chain_type.unwrap().try_into().unwrap();
 
+        let key_id_mode: KeyIdMode = merged_config_option
+            .key_id_mode
+            .as_deref()
+            .unwrap_or(DEFAULT_KEY_ID_MODE)
+            .parse()?;
+        if key_id_mode == KeyIdMode::Slot && merged_config_option.genesis_time.is_none() {
+            return Err(ConfigError::MissingGenesisTime);
+        }
+
+        let round_schedule: RoundSchedule = merged_config_option
+            .round_schedule
+            .as_deref()
+            .unwrap_or(DEFAULT_ROUND_SCHEDULE)
+            .parse()?;
+        if round_schedule == RoundSchedule::BlockHeight
+            && merged_config_option.chain_rpc_url.is_none()
+        {
+            return Err(ConfigError::MissingChainRpcUrl);
+        }
+        if merged_config_option.registry_contract_address.is_some()
+            && merged_config_option.chain_rpc_url.is_none()
+        {
+            return Err(ConfigError::MissingChainRpcUrl);
+        }
+
+        let key_publisher: KeyPublisherKind = merged_config_option
+            .key_publisher
+            .as_deref()
+            .unwrap_or(DEFAULT_KEY_PUBLISHER)
+            .parse()?;
+        match key_publisher {
+            KeyPublisherKind::None => {}
+            KeyPublisherKind::Ethereum => {
+                if merged_config_option.chain_rpc_url.is_none() {
+                    return Err(ConfigError::MissingChainRpcUrl);
+                }
+                if merged_config_option
+                    .key_publisher_contract_address
+                    .is_none()
+                {
+                    return Err(ConfigError::MissingKeyPublisherSetting(
+                        "key_publisher_contract_address",
+                    ));
+                }
+            }
+            KeyPublisherKind::Http => {
+                if merged_config_option.key_publisher_url.is_none() {
+                    return Err(ConfigError::MissingKeyPublisherSetting("key_publisher_url"));
+                }
+            }
+            KeyPublisherKind::File => {
+                if merged_config_option.key_publisher_file_path.is_none() {
+                    return Err(ConfigError::MissingKeyPublisherSetting(
+                        "key_publisher_file_path",
+                    ));
+                }
+            }
+        }
+
+        let cluster_tls = match (
+            merged_config_option.cluster_tls_cert_path.clone(),
+            merged_config_option.cluster_tls_key_path.clone(),
+            merged_config_option.cluster_tls_ca_path.clone(),
+        ) {
+            (Some(cert_path), Some(key_path), Some(ca_path)) => Some(TlsFiles {
+                cert_path,
+                key_path,
+                ca_path,
+            }),
+            (None, None, None) => None,
+            _ => return Err(ConfigError::IncompleteTlsSettings),
+        };
+        let external_tls = match (
+            merged_config_option.external_tls_cert_path.clone(),
+            merged_config_option.external_tls_key_path.clone(),
+        ) {
+            (Some(cert_path), Some(key_path)) => Some(ExternalTlsFiles {
+                cert_path,
+                key_path,
+            }),
+            (None, None) => None,
+            _ => return Err(ConfigError::IncompleteTlsSettings),
+        };
+
         // Read signing key
         let signing_key_path = config_path.join(SIGNING_KEY);
         let signer =
@@ -75,6 +214,7 @@ impl Config {
             external_rpc_url: merged_config_option.external_rpc_url.unwrap(),
             internal_rpc_url: merged_config_option.internal_rpc_url.unwrap(),
             cluster_rpc_url: merged_config_option.cluster_rpc_url.unwrap(),
+            health_check_url: merged_config_option.health_check_url.clone(),
             seed_cluster_rpc_url: merged_config_option.seed_cluster_rpc_url.clone(),
             signer,
             radius_foundation_address: Address::from_str(
@@ -90,6 +230,77 @@ impl Config {
             partial_key_aggregation_cycle: merged_config_option
                 .partial_key_aggregation_cycle
                 .unwrap(),
+            round_schedule,
+            key_id_mode,
+            genesis_time: merged_config_option.genesis_time,
+            chain_rpc_url: merged_config_option.chain_rpc_url.clone(),
+            blocks_per_key: merged_config_option
+                .blocks_per_key
+                .unwrap_or(DEFAULT_BLOCKS_PER_KEY)
+                .max(1),
+            registry_contract_address: merged_config_option.registry_contract_address.clone(),
+            registry_sync_interval_blocks: merged_config_option
+                .registry_sync_interval_blocks
+                .unwrap_or(DEFAULT_REGISTRY_SYNC_INTERVAL_BLOCKS)
+                .max(1),
+
+            key_publisher,
+            key_publisher_contract_address: merged_config_option
+                .key_publisher_contract_address
+                .clone(),
+            key_publisher_url: merged_config_option.key_publisher_url.clone(),
+            key_publisher_file_path: merged_config_option.key_publisher_file_path.clone(),
+
+            webhook_urls: merged_config_option.webhook_urls.unwrap_or_default(),
+            enable_encryption_rpc: merged_config_option
+                .enable_encryption_rpc
+                .unwrap_or_default(),
+            external_rpc_limits: RpcLimits {
+                rate_limit_per_second: merged_config_option.external_rate_limit_per_second,
+                rate_limit_burst: merged_config_option
+                    .external_rate_limit_burst
+                    .or(merged_config_option.external_rate_limit_per_second)
+                    .unwrap_or_default(),
+                max_body_size: merged_config_option
+                    .external_max_body_size
+                    .unwrap_or(DEFAULT_EXTERNAL_MAX_BODY_SIZE),
+                max_concurrent_requests: merged_config_option
+                    .external_max_concurrent_requests
+                    .unwrap_or(DEFAULT_EXTERNAL_MAX_CONCURRENT_REQUESTS)
+                    .max(1),
+                max_connections_per_ip: merged_config_option
+                    .external_max_connections_per_ip
+                    .unwrap_or(DEFAULT_EXTERNAL_MAX_CONNECTIONS_PER_IP)
+                    .max(1),
+                api_keys: merged_config_option
+                    .external_api_keys
+                    .clone()
+                    .unwrap_or_default(),
+            },
+            cluster_tls,
+            external_tls,
+            external_upstream_port: merged_config_option
+                .external_upstream_port
+                .unwrap_or(DEFAULT_EXTERNAL_UPSTREAM_PORT),
+            cluster_upstream_port: merged_config_option
+                .cluster_upstream_port
+                .unwrap_or(DEFAULT_CLUSTER_UPSTREAM_PORT),
+
+            log_level: merged_config_option
+                .log_level
+                .unwrap_or_else(|| DEFAULT_LOG_LEVEL.into()),
+            log_format: merged_config_option
+                .log_format
+                .as_deref()
+                .unwrap_or(DEFAULT_LOG_FORMAT)
+                .parse()?,
+            log_file_path: merged_config_option.log_file_path,
+            log_rotation: merged_config_option
+                .log_rotation
+                .as_deref()
+                .unwrap_or(DEFAULT_LOG_ROTATION)
+                .parse()?,
+            log_secrets: merged_config_option.log_secrets.unwrap_or_default(),
         })
     }
 
@@ -133,6 +344,97 @@ impl Config {
         self.partial_key_aggregation_cycle
     }
 
+    pub fn round_schedule(&self) -> RoundSchedule {
+        self.round_schedule
+    }
+
+    /// The clock key ids follow, if they are derived from wall-clock slots.
+    pub fn key_slot_clock(&self) -> Option<KeySlotClock> {
+        match (self.key_id_mode, self.genesis_time) {
+            (KeyIdMode::Slot, Some(genesis_time)) => Some(KeySlotClock::new(
+                genesis_time,
+                self.partial_key_generation_cycle,
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn chain_rpc_url(&self) -> &Option<String> {
+        &self.chain_rpc_url
+    }
+
+    pub fn blocks_per_key(&self) -> u64 {
+        self.blocks_per_key
+    }
+
+    /// Set when the cluster membership is read from a registry contract
+    /// instead of being managed through the internal RPC.
+    pub fn registry_contract_address(&self) -> &Option<String> {
+        &self.registry_contract_address
+    }
+
+    pub fn registry_sync_interval_blocks(&self) -> u64 {
+        self.registry_sync_interval_blocks
+    }
+
+    pub fn key_publisher(&self) -> KeyPublisherKind {
+        self.key_publisher
+    }
+
+    pub fn key_publisher_contract_address(&self) -> &Option<String> {
+        &self.key_publisher_contract_address
+    }
+
+    pub fn key_publisher_url(&self) -> &Option<String> {
+        &self.key_publisher_url
+    }
+
+    pub fn key_publisher_file_path(&self) -> &Option<PathBuf> {
+        &self.key_publisher_file_path
+    }
+
+    pub fn webhook_urls(&self) -> &Vec<String> {
+        &self.webhook_urls
+    }
+
+    pub fn enable_encryption_rpc(&self) -> bool {
+        self.enable_encryption_rpc
+    }
+
+    pub fn external_rpc_limits(&self) -> &RpcLimits {
+        &self.external_rpc_limits
+    }
+
+    /// Set when cluster traffic uses mutual TLS.
+    pub fn cluster_tls(&self) -> &Option<TlsFiles> {
+        &self.cluster_tls
+    }
+
+    /// Set when the external port serves TLS.
+    pub fn external_tls(&self) -> &Option<ExternalTlsFiles> {
+        &self.external_tls
+    }
+
+    pub fn log_level(&self) -> &str {
+        &self.log_level
+    }
+
+    pub fn log_format(&self) -> LogFormat {
+        self.log_format
+    }
+
+    pub fn log_file_path(&self) -> &Option<PathBuf> {
+        &self.log_file_path
+    }
+
+    pub fn log_rotation(&self) -> LogRotation {
+        self.log_rotation
+    }
+
+    pub fn log_secrets(&self) -> bool {
+        self.log_secrets
+    }
+
     pub fn cluster_rpc_url(&self) -> &String {
         &self.cluster_rpc_url
     }
@@ -141,6 +443,10 @@ impl Config {
         &self.seed_cluster_rpc_url
     }
 
+    pub fn health_check_url(&self) -> &Option<String> {
+        &self.health_check_url
+    }
+
     pub fn external_port(&self) -> Result<String, ConfigError> {
         Ok(self
             .external_rpc_url()
@@ -150,6 +456,25 @@ impl Config {
             .to_string())
     }
 
+    /// The `host:port` the health server listens on. Unlike the RPC servers
+    /// it binds to the host of `health_check_url` (the loopback interface by
+    /// default), since `/metrics` is not meant to be public.
+    pub fn health_check_address(&self) -> Result<Option<String>, ConfigError> {
+        self.health_check_url()
+            .as_ref()
+            .map(|health_check_url| {
+                let address = health_check_url
+                    .split_once("://")
+                    .map_or(health_check_url.as_str(), |(_, address)| address);
+                if !address.contains(':') {
+                    return Err(ConfigError::InvalidHealthCheckPort);
+                }
+
+                Ok(address.trim_end_matches('/').to_string())
+            })
+            .transpose()
+    }
+
     pub fn cluster_port(&self) -> Result<String, ConfigError> {
         Ok(self
             .cluster_rpc_url()
@@ -158,6 +483,18 @@ impl Config {
             .ok_or(ConfigError::InvalidClusterPort)?
             .to_string())
     }
+
+    /// The loopback `host:port` of the external RPC server, which only the
+    /// external gateway connects to.
+    pub fn external_upstream_address(&self) -> String {
+        format!("127.0.0.1:{}", self.external_upstream_port)
+    }
+
+    /// The loopback `host:port` of the cluster RPC server when the cluster
+    /// TLS gateway sits in front of it.
+    pub fn cluster_upstream_address(&self) -> String {
+        format!("127.0.0.1:{}", self.cluster_upstream_port)
+    }
 }
 
 #[derive(Debug)]
@@ -171,6 +508,16 @@ pub enum ConfigError {
 
     InvalidExternalPort,
     InvalidClusterPort,
+    InvalidHealthCheckPort,
+    InvalidLogFormat(String),
+    InvalidLogRotation(String),
+    InvalidRoundSchedule(String),
+    InvalidKeyIdMode(String),
+    MissingGenesisTime,
+    MissingChainRpcUrl,
+    InvalidKeyPublisher(String),
+    MissingKeyPublisherSetting(&'static str),
+    IncompleteTlsSettings,
 }
 
 impl std::fmt::Display for ConfigError {
@@ -0,0 +1,30 @@
+use std::str::FromStr;
+
+use super::ConfigError;
+
+/// Where the leader publishes the commitment of every aggregated key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyPublisherKind {
+    None,
+    /// A `publishKeyCommitment` transaction to
+    /// `key_publisher_contract_address` sent through `chain_rpc_url`.
+    Ethereum,
+    /// A JSON POST to `key_publisher_url`.
+    Http,
+    /// A JSON line appended to `key_publisher_file_path`.
+    File,
+}
+
+impl FromStr for KeyPublisherKind {
+    type Err = ConfigError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(Self::None),
+            "ethereum" => Ok(Self::Ethereum),
+            "http" => Ok(Self::Http),
+            "file" => Ok(Self::File),
+            _ => Err(ConfigError::InvalidKeyPublisher(value.to_owned())),
+        }
+    }
+}
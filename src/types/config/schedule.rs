@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use super::ConfigError;
+
+/// How the leader decides when a key generation round starts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundSchedule {
+    /// One round every `partial_key_generation_cycle` seconds after the
+    /// previous one.
+    FixedInterval,
+    /// Rounds start at every multiple of `partial_key_generation_cycle`
+    /// seconds since the Unix epoch.
+    WallClock,
+    /// Rounds start only when `trigger_round` is called.
+    External,
+    /// A round starts whenever the height reported by `chain_rpc_url` enters
+    /// the next range of `blocks_per_key` blocks.
+    BlockHeight,
+}
+
+impl FromStr for RoundSchedule {
+    type Err = ConfigError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "fixed_interval" => Ok(Self::FixedInterval),
+            "wall_clock" => Ok(Self::WallClock),
+            "external" => Ok(Self::External),
+            "block_height" => Ok(Self::BlockHeight),
+            _ => Err(ConfigError::InvalidRoundSchedule(value.to_owned())),
+        }
+    }
+}
+
+/// Where the key id of a new round comes from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyIdMode {
+    /// A counter stored and incremented by the leader.
+    Counter,
+    /// The wall-clock slot since `genesis_time`, see
+    /// [`KeySlotClock`](crate::types::KeySlotClock).
+    Slot,
+}
+
+impl FromStr for KeyIdMode {
+    type Err = ConfigError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "counter" => Ok(Self::Counter),
+            "slot" => Ok(Self::Slot),
+            _ => Err(ConfigError::InvalidKeyIdMode(value.to_owned())),
+        }
+    }
+}
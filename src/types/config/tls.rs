@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+/// PEM files of a node's cluster identity. The certificate must be issued by
+/// the cluster CA and bound to the node's key generator address, see
+/// [`address_server_name`](crate::tls::address_server_name).
+#[derive(Clone, Debug)]
+pub struct TlsFiles {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    pub ca_path: PathBuf,
+}
+
+/// PEM files the external port serves TLS with. Public clients check them
+/// against the node's real hostname, so they usually come from a public CA
+/// rather than the cluster CA.
+#[derive(Clone, Debug)]
+pub struct ExternalTlsFiles {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
@@ -1,19 +1,19 @@
 use std::collections::HashSet;
 
-use radius_sdk::{
-    kvstore::{KvStoreError, Model},
-    signature::Address,
-};
+use radius_sdk::{kvstore::KvStoreError, signature::Address};
 use serde::{Deserialize, Serialize};
 use skde::{
     key_aggregation::AggregatedKey as SkdeAggregatedKey,
     key_generation::PartialKey as SkdePartialKey,
 };
 
-#[derive(Clone, Debug, Deserialize, Serialize, Model)]
-#[kvstore(key(key_id: KeyId, address: &Address))]
+use crate::database::model;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PartialKey(SkdePartialKey);
 
+model!(PartialKey, key(key_id: KeyId, address: &Address));
+
 impl PartialKey {
     pub fn new(partial_key: SkdePartialKey) -> Self {
         Self(partial_key)
@@ -24,11 +24,11 @@ impl PartialKey {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize, Model)]
-#[kvstore(key(key_id: KeyId))]
-
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct PartialKeyAddressList(HashSet<Address>);
 
+model!(PartialKeyAddressList, key(key_id: KeyId));
+
 impl PartialKeyAddressList {
     pub fn default() -> Self {
         Self(HashSet::new())
@@ -42,6 +42,10 @@ impl PartialKeyAddressList {
         self.0.remove(&address);
     }
 
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.contains(address)
+    }
+
     pub fn to_vec(&self) -> Vec<Address> {
         self.0.iter().cloned().collect()
     }
@@ -74,11 +78,16 @@ impl PartialKeyAddressList {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, Model)]
-#[kvstore(key())]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct KeyId(u64);
 
+model!(KeyId, key());
+
 impl KeyId {
+    pub fn new(key_id: u64) -> Self {
+        Self(key_id)
+    }
+
     pub fn default() -> Self {
         Self(0)
     }
@@ -98,7 +107,7 @@ impl KeyId {
     }
 
     pub fn decrease_key_id(&mut self) {
-        self.0 -= 1;
+        self.0 = self.0.saturating_sub(1);
     }
 
     pub fn as_u64(self) -> u64 {
@@ -106,10 +115,100 @@ impl KeyId {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Model)]
-#[kvstore(key(key_id: KeyId))]
+/// How many key ids [`LatestAggregatedKeyId::initialize`] looks back.
+pub const LATEST_AGGREGATED_KEY_ID_LOOKBACK: u64 = 1024;
+
+/// Points at the highest [`KeyId`] for which an [`AggregatedKey`] has been
+/// stored. Absent until the first aggregation completes.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LatestAggregatedKeyId(KeyId);
+
+model!(LatestAggregatedKeyId, key());
+
+impl LatestAggregatedKeyId {
+    /// Builds the pointer for databases created before it existed by walking
+    /// down from the current [`KeyId`] once. Slot key ids are far apart from
+    /// 0, so the walk stops after [`LATEST_AGGREGATED_KEY_ID_LOOKBACK`] ids
+    /// and the pointer is then left to the next aggregation.
+    pub fn initialize() -> Result<(), KvStoreError> {
+        if Self::get().is_ok() {
+            return Ok(());
+        }
+
+        let mut key_id = KeyId::get()?;
+        for _ in 0..LATEST_AGGREGATED_KEY_ID_LOOKBACK {
+            if AggregatedKey::get(key_id).is_ok() {
+                return Self(key_id).put();
+            }
+
+            if key_id.as_u64() == 0 {
+                break;
+            }
+
+            key_id.decrease_key_id();
+        }
+
+        Ok(())
+    }
+
+    /// Moves the pointer to `key_id` unless it already points at a newer key.
+    /// Aggregations may complete out of order, so the pointer never goes back.
+    pub fn advance(key_id: KeyId) -> Result<(), KvStoreError> {
+        Self::apply_or(
+            || Self(key_id),
+            |latest_aggregated_key_id| {
+                if key_id.as_u64() > latest_aggregated_key_id.0.as_u64() {
+                    latest_aggregated_key_id.0 = key_id;
+                }
+            },
+        )
+    }
+
+    pub fn key_id(&self) -> KeyId {
+        self.0
+    }
+}
+
+/// Points at the highest [`KeyId`] for which a [`DecryptionKey`] has been
+/// stored. Absent until the first time-lock puzzle is solved.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LatestDecryptionKeyId(KeyId);
+
+model!(LatestDecryptionKeyId, key());
+
+impl LatestDecryptionKeyId {
+    /// Moves the pointer to `key_id` unless it already points at a newer key.
+    pub fn advance(key_id: KeyId) -> Result<(), KvStoreError> {
+        Self::apply_or(
+            || Self(key_id),
+            |latest_decryption_key_id| {
+                if key_id.as_u64() > latest_decryption_key_id.0.as_u64() {
+                    latest_decryption_key_id.0 = key_id;
+                }
+            },
+        )
+    }
+
+    pub fn key_id(&self) -> KeyId {
+        self.0
+    }
+}
+
+#[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DecryptionKey(String);
 
+model!(DecryptionKey, key(key_id: KeyId));
+
+/// Redacted unless `log_secrets` is set, since decryption keys are printed
+/// in logs before they are meant to be public.
+impl std::fmt::Debug for DecryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DecryptionKey")
+            .field(&crate::logger::Secret(&self.0))
+            .finish()
+    }
+}
+
 impl DecryptionKey {
     pub fn new(decryption_key: String) -> Self {
         Self(decryption_key)
@@ -120,10 +219,11 @@ impl DecryptionKey {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Model)]
-#[kvstore(key(key_id: KeyId))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AggregatedKey(SkdeAggregatedKey);
 
+model!(AggregatedKey, key(key_id: KeyId));
+
 impl AggregatedKey {
     pub fn new(aggregated_key: SkdeAggregatedKey) -> Self {
         Self(aggregated_key)
@@ -0,0 +1,158 @@
+use alloy_primitives::keccak256;
+use radius_sdk::signature::Address;
+
+use crate::{
+    database::model,
+    types::{prelude::*, KeyId},
+};
+
+/// What gets published for an aggregated key: the key id, the keccak-256
+/// hash of the encryption key `u` and the key generators that contributed a
+/// partial key.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct KeyCommitment {
+    pub key_id: KeyId,
+    /// `0x` prefixed keccak-256 hash of the encryption key.
+    pub key_hash: String,
+    pub participants: Vec<Address>,
+}
+
+impl KeyCommitment {
+    pub fn new(key_id: KeyId, encryption_key: &str, mut participants: Vec<Address>) -> Self {
+        participants.sort_by_key(|address| address.as_hex_string());
+
+        Self {
+            key_id,
+            key_hash: key_hash(encryption_key),
+            participants,
+        }
+    }
+}
+
+/// `0x` prefixed keccak-256 hash of `encryption_key`.
+pub fn key_hash(encryption_key: &str) -> String {
+    const_hex::encode_prefixed(keccak256(encryption_key.as_bytes()))
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishState {
+    Pending,
+    Published,
+    /// Gave up after the maximum number of attempts.
+    Failed,
+}
+
+/// Publish progress of the [`KeyCommitment`] of a key id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublishStatus {
+    pub commitment: KeyCommitment,
+    pub state: PublishState,
+    pub attempts: u32,
+    /// Unix timestamp (seconds) before which a pending commitment is not
+    /// retried.
+    pub next_attempt_at: u64,
+    /// Sink specific proof of publication, e.g. a transaction hash.
+    pub receipt: Option<String>,
+    pub last_error: Option<String>,
+}
+
+model!(PublishStatus, key(key_id: KeyId));
+
+impl PublishStatus {
+    pub fn new(commitment: KeyCommitment, now: u64) -> Self {
+        Self {
+            commitment,
+            state: PublishState::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            receipt: None,
+            last_error: None,
+        }
+    }
+
+    pub fn record_success(&mut self, receipt: String) {
+        self.attempts = self.attempts.saturating_add(1);
+        self.state = PublishState::Published;
+        self.receipt = Some(receipt);
+        self.last_error = None;
+    }
+
+    /// Schedules the next attempt after `backoff` seconds, or marks the
+    /// commitment as failed once `max_attempts` is reached.
+    pub fn record_failure(&mut self, error: String, now: u64, backoff: u64, max_attempts: u32) {
+        self.attempts = self.attempts.saturating_add(1);
+        self.next_attempt_at = now.saturating_add(backoff);
+        self.last_error = Some(error);
+
+        if self.attempts >= max_attempts {
+            self.state = PublishState::Failed;
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.state == PublishState::Pending && self.next_attempt_at <= now
+    }
+}
+
+/// Signed transaction carrying the commitment of a key id, kept until it is
+/// mined so that a retry sends the same transaction, nonce included, rather
+/// than a second one.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CommitmentTransaction {
+    pub nonce: u64,
+    pub gas_price: u64,
+    pub gas_limit: u64,
+    /// `0x` prefixed transaction hash.
+    pub hash: String,
+    /// `0x` prefixed EIP-2718 encoding of the signed transaction.
+    pub raw_transaction: String,
+    /// Attempts that found the transaction not mined yet.
+    #[serde(default)]
+    pub not_mined_attempts: u32,
+    /// Hashes of earlier transactions at the same nonce that this one
+    /// replaced with a higher gas price. Any of them may still be mined.
+    #[serde(default)]
+    pub replaced_hashes: Vec<String>,
+}
+
+model!(CommitmentTransaction, key(key_id: KeyId));
+
+impl CommitmentTransaction {
+    /// The hash of this transaction, then those of the ones it replaced.
+    pub fn hashes(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.hash.as_str()).chain(self.replaced_hashes.iter().map(String::as_str))
+    }
+}
+
+/// Key ids whose commitment has not been published yet, oldest first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct KeyPublishQueue(Vec<KeyId>);
+
+model!(KeyPublishQueue, key());
+
+impl KeyPublishQueue {
+    pub fn initialize() -> Result<(), KvStoreError> {
+        if Self::get().is_err() {
+            let key_publish_queue = Self::default();
+
+            key_publish_queue.put()?
+        }
+
+        Ok(())
+    }
+
+    pub fn push(&mut self, key_id: KeyId) {
+        if !self.0.contains(&key_id) {
+            self.0.push(key_id);
+        }
+    }
+
+    pub fn remove(&mut self, key_id: KeyId) {
+        self.0.retain(|queued_key_id| *queued_key_id != key_id);
+    }
+
+    pub fn to_vec(&self) -> Vec<KeyId> {
+        self.0.clone()
+    }
+}
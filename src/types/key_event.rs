@@ -0,0 +1,29 @@
+use crate::types::{prelude::*, KeyId};
+
+/// Emitted by the node whenever a new key becomes available locally.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KeyEvent {
+    EncryptionKeyReady {
+        key_id: KeyId,
+        encryption_key: String,
+    },
+    DecryptionKeyReady {
+        key_id: KeyId,
+        decryption_key: String,
+    },
+    RoundFailed {
+        key_id: KeyId,
+        reason: String,
+    },
+}
+
+impl KeyEvent {
+    pub fn key_id(&self) -> KeyId {
+        match self {
+            Self::EncryptionKeyReady { key_id, .. } => *key_id,
+            Self::DecryptionKeyReady { key_id, .. } => *key_id,
+            Self::RoundFailed { key_id, .. } => *key_id,
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::{hash_set::Iter, HashSet};
 
-use radius_sdk::{kvstore::Model, signature::Address};
+use radius_sdk::signature::Address;
 
-use crate::{rpc::cluster::KeyGeneratorRpcInfo, types::prelude::*};
+use crate::{
+    database::model,
+    rpc::cluster::KeyGeneratorRpcInfo,
+    types::{prelude::*, PeerLiveness},
+};
 
 #[derive(Clone, Hash, Eq, PartialEq, Debug, Deserialize, Serialize)]
 
@@ -34,19 +38,28 @@ impl KeyGenerator {
     }
 }
 
-impl From<KeyGeneratorRpcInfo> for KeyGenerator {
-    fn from(info: KeyGeneratorRpcInfo) -> Self {
-        let decoded_bytes =
-            const_hex::decode(&info.address).expect("Invalid hex string in KeyGeneratorRpcInfo");
+/// The address comes from another node, so a malformed one is an error rather
+/// than a panic.
+impl TryFrom<KeyGeneratorRpcInfo> for KeyGenerator {
+    type Error = const_hex::FromHexError;
+
+    fn try_from(info: KeyGeneratorRpcInfo) -> Result<Self, Self::Error> {
+        let decoded_bytes = const_hex::decode(&info.address)?;
         let address: Address = Address::from(decoded_bytes);
-        KeyGenerator::new(address, info.cluster_rpc_url, info.external_rpc_url)
+
+        Ok(KeyGenerator::new(
+            address,
+            info.cluster_rpc_url,
+            info.external_rpc_url,
+        ))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Model)]
-#[kvstore(key())]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct KeyGeneratorList(HashSet<KeyGenerator>);
 
+model!(KeyGeneratorList, key());
+
 impl KeyGeneratorList {
     pub fn default() -> Self {
         Self(HashSet::new())
@@ -98,6 +111,43 @@ impl KeyGeneratorList {
             .collect()
     }
 
+    /// Cluster RPC urls of every key generator except `my_address` and the
+    /// peers currently considered dead.
+    pub fn get_other_alive_key_generator_rpc_url_list(&self, my_address: &Address) -> Vec<String> {
+        self.iter()
+            .filter(|key_generator| key_generator.address() != my_address)
+            .filter(|key_generator| PeerLiveness::is_key_generator_alive(key_generator))
+            .map(|key_generator| key_generator.cluster_rpc_url().to_owned())
+            .collect()
+    }
+
+    /// Cluster RPC urls of this node and every peer currently considered
+    /// alive.
+    pub fn get_all_alive_key_generator_rpc_url_list(&self, my_address: &Address) -> Vec<String> {
+        self.iter()
+            .filter(|key_generator| {
+                key_generator.address() == my_address
+                    || PeerLiveness::is_key_generator_alive(key_generator)
+            })
+            .map(|key_generator| key_generator.cluster_rpc_url().to_owned())
+            .collect()
+    }
+
+    /// Replaces the list with `key_generators` and returns what changed.
+    pub fn reconcile(
+        &mut self,
+        key_generators: impl IntoIterator<Item = KeyGenerator>,
+    ) -> MembershipChange {
+        let key_generators: HashSet<KeyGenerator> = key_generators.into_iter().collect();
+
+        let added: Vec<KeyGenerator> = key_generators.difference(&self.0).cloned().collect();
+        let removed: Vec<KeyGenerator> = self.0.difference(&key_generators).cloned().collect();
+
+        self.0 = key_generators;
+
+        MembershipChange { added, removed }
+    }
+
     pub fn initialize() -> Result<(), KvStoreError> {
         if Self::get().is_err() {
             let key_generator_list = Self::default();
@@ -109,9 +159,27 @@ impl KeyGeneratorList {
     }
 }
 
-impl From<Vec<KeyGeneratorRpcInfo>> for KeyGeneratorList {
-    fn from(info_vec: Vec<KeyGeneratorRpcInfo>) -> Self {
-        let set: HashSet<KeyGenerator> = info_vec.into_iter().map(Into::into).collect();
-        KeyGeneratorList(set)
+#[derive(Clone, Debug, Default)]
+pub struct MembershipChange {
+    pub added: Vec<KeyGenerator>,
+    pub removed: Vec<KeyGenerator>,
+}
+
+impl MembershipChange {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl TryFrom<Vec<KeyGeneratorRpcInfo>> for KeyGeneratorList {
+    type Error = const_hex::FromHexError;
+
+    fn try_from(info_vec: Vec<KeyGeneratorRpcInfo>) -> Result<Self, Self::Error> {
+        let set = info_vec
+            .into_iter()
+            .map(KeyGenerator::try_from)
+            .collect::<Result<HashSet<KeyGenerator>, _>>()?;
+
+        Ok(KeyGeneratorList(set))
     }
 }
@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+
+use radius_sdk::signature::{Address, ChainType, PrivateKeySigner, Signature, SignatureError};
+use sha2::{Digest, Sha256};
+use skde::delay_encryption::SkdeParams;
+
+use crate::{
+    database::model,
+    types::{prelude::*, KeyId},
+};
+
+/// The message a key generator signs when it hands out a key, binding the key
+/// to its id and to the SKDE parameters it was generated under.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct KeySignatureMessage {
+    pub key_id: KeyId,
+    pub key: String,
+    pub skde_params_hash: String,
+}
+
+impl KeySignatureMessage {
+    pub fn new(key_id: KeyId, key: String, skde_params_hash: String) -> Self {
+        Self {
+            key_id,
+            key,
+            skde_params_hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KeySignature {
+    pub signer: Address,
+    pub signature: Signature,
+}
+
+impl KeySignature {
+    pub fn sign(
+        signer: &PrivateKeySigner,
+        message: &KeySignatureMessage,
+    ) -> Result<Self, SignatureError> {
+        Ok(Self {
+            signer: signer.address().clone(),
+            signature: signer.sign_message(message)?,
+        })
+    }
+
+    pub fn verify(
+        &self,
+        chain_type: ChainType,
+        message: &KeySignatureMessage,
+    ) -> Result<(), SignatureError> {
+        self.signature
+            .verify_message(chain_type, message, &self.signer)
+    }
+}
+
+/// Signatures of key generators over the same [`KeySignatureMessage`] for an
+/// aggregated key. Once it holds signatures from a threshold of registered
+/// key generators it certifies that they agreed on the encryption key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EncryptionKeyCertificate {
+    pub message: KeySignatureMessage,
+    pub key_signatures: Vec<KeySignature>,
+}
+
+model!(EncryptionKeyCertificate, key(key_id: KeyId));
+
+impl EncryptionKeyCertificate {
+    /// Adds an already verified signature over `message`. A signer is only
+    /// recorded once; a certificate for a different key is replaced.
+    pub fn add_signature(
+        message: &KeySignatureMessage,
+        key_signature: KeySignature,
+    ) -> Result<(), KvStoreError> {
+        let new_certificate = || Self {
+            message: message.clone(),
+            key_signatures: Vec::new(),
+        };
+
+        Self::apply_or(message.key_id, new_certificate, |certificate| {
+            if &certificate.message != message {
+                certificate.message = message.clone();
+                certificate.key_signatures.clear();
+            }
+
+            if !certificate
+                .key_signatures
+                .iter()
+                .any(|existing| existing.signer == key_signature.signer)
+            {
+                certificate.key_signatures.push(key_signature);
+            }
+        })
+    }
+
+    pub fn verify(
+        &self,
+        chain_type: ChainType,
+        key_generators: &[Address],
+        threshold: usize,
+    ) -> Result<usize, KeyQuorumError> {
+        verify_key_quorum(
+            chain_type,
+            &self.message,
+            &self.key_signatures,
+            key_generators,
+            threshold,
+        )
+    }
+}
+
+/// Signatures received for a key id before this node aggregated its own key.
+/// They are checked against the key once it is aggregated.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PendingKeySignatures(Vec<KeySignature>);
+
+model!(PendingKeySignatures, key(key_id: KeyId));
+
+impl PendingKeySignatures {
+    /// Keeps `key_signature` until the key of `key_id` is aggregated. A signer
+    /// is only kept once.
+    pub fn push(key_id: KeyId, key_signature: KeySignature) -> Result<(), KvStoreError> {
+        Self::apply_or(key_id, Self::default, |pending_key_signatures| {
+            if !pending_key_signatures
+                .0
+                .iter()
+                .any(|existing| existing.signer == key_signature.signer)
+            {
+                pending_key_signatures.0.push(key_signature);
+            }
+        })
+    }
+
+    /// Removes and returns the signatures kept for `key_id`.
+    pub fn take(key_id: KeyId) -> Result<Vec<KeySignature>, KvStoreError> {
+        let Ok(mut pending_key_signatures) = Self::get_mut(key_id) else {
+            return Ok(Vec::new());
+        };
+        let key_signatures = std::mem::take(&mut pending_key_signatures.0);
+        pending_key_signatures.update()?;
+
+        Ok(key_signatures)
+    }
+}
+
+/// Hex-encoded SHA-256 over the JSON encoding of `skde_params`, as returned by
+/// `get_skde_params`.
+pub fn skde_params_hash(skde_params: &SkdeParams) -> Result<String, serde_json::Error> {
+    let encoded = serde_json::to_vec(skde_params)?;
+
+    Ok(const_hex::encode(Sha256::digest(encoded)))
+}
+
+/// Checks that at least `threshold` distinct members of `key_generators`
+/// signed `message`. Signatures from unknown or duplicate signers and invalid
+/// signatures are ignored. Returns the number of valid signers.
+pub fn verify_key_quorum(
+    chain_type: ChainType,
+    message: &KeySignatureMessage,
+    key_signatures: &[KeySignature],
+    key_generators: &[Address],
+    threshold: usize,
+) -> Result<usize, KeyQuorumError> {
+    let mut valid_signers = HashSet::new();
+
+    for key_signature in key_signatures {
+        if !key_generators.contains(&key_signature.signer)
+            || valid_signers.contains(&key_signature.signer)
+        {
+            continue;
+        }
+
+        if key_signature.verify(chain_type, message).is_ok() {
+            valid_signers.insert(key_signature.signer.clone());
+        }
+    }
+
+    if valid_signers.len() < threshold {
+        return Err(KeyQuorumError {
+            valid_signer_count: valid_signers.len(),
+            threshold,
+        });
+    }
+
+    Ok(valid_signers.len())
+}
+
+#[derive(Debug)]
+pub struct KeyQuorumError {
+    pub valid_signer_count: usize,
+    pub threshold: usize,
+}
+
+impl std::fmt::Display for KeyQuorumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for KeyQuorumError {}
@@ -0,0 +1,35 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::{prelude::*, KeyId};
+
+/// Maps wall-clock time to key ids: key id `n` covers
+/// `[genesis_time + n * cycle, genesis_time + (n + 1) * cycle)`. Anyone who
+/// knows both values can compute the current key id without asking a node.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct KeySlotClock {
+    /// Unix timestamp (seconds) at which key id 0 starts.
+    pub genesis_time: u64,
+    /// Length of a slot in seconds.
+    pub cycle: u64,
+}
+
+impl KeySlotClock {
+    pub fn new(genesis_time: u64, cycle: u64) -> Self {
+        Self {
+            genesis_time,
+            cycle: cycle.max(1),
+        }
+    }
+
+    /// Key id of the slot containing `time`, or `None` before genesis.
+    pub fn key_id_at(&self, time: SystemTime) -> Option<KeyId> {
+        let now = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let since_genesis = now.checked_sub(self.genesis_time)?;
+
+        Some(KeyId::new(since_genesis / self.cycle))
+    }
+
+    pub fn current_key_id(&self) -> Option<KeyId> {
+        self.key_id_at(SystemTime::now())
+    }
+}
@@ -1,10 +1,24 @@
+mod block_range;
 mod config;
 mod key;
+mod key_commitment;
+mod key_event;
 mod key_generator;
+mod key_signature;
+mod key_slot;
+mod peer_liveness;
+mod webhook;
 
+pub use block_range::*;
 pub use config::*;
 pub use key::*;
+pub use key_commitment::*;
+pub use key_event::*;
 pub use key_generator::*;
+pub use key_signature::*;
+pub use key_slot::*;
+pub use peer_liveness::*;
+pub use webhook::*;
 
 pub(crate) mod prelude {
     pub use radius_sdk::kvstore::KvStoreError;
@@ -0,0 +1,51 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use radius_sdk::signature::Address;
+
+use crate::{
+    database::model,
+    types::{prelude::*, KeyGenerator},
+};
+
+/// Consecutive missed heartbeats after which a peer is considered dead.
+pub const DEAD_PEER_FAILURE_THRESHOLD: u32 = 3;
+
+/// Heartbeat history of a single key generator, as observed by this node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PeerLiveness {
+    /// Unix timestamp (seconds) of the last answered heartbeat.
+    pub last_seen_at: Option<u64>,
+    /// Round trip time of the last answered heartbeat.
+    pub rtt_ms: Option<u64>,
+    pub consecutive_failures: u32,
+}
+
+model!(PeerLiveness, key(address: &Address));
+
+impl PeerLiveness {
+    pub fn record_success(&mut self, rtt_ms: u64) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        self.last_seen_at = Some(now);
+        self.rtt_ms = Some(rtt_ms);
+        self.consecutive_failures = 0;
+    }
+
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.consecutive_failures < DEAD_PEER_FAILURE_THRESHOLD
+    }
+
+    /// Peers that were never probed are given the benefit of the doubt.
+    pub fn is_key_generator_alive(key_generator: &KeyGenerator) -> bool {
+        Self::get(key_generator.address())
+            .map(|peer_liveness| peer_liveness.is_alive())
+            .unwrap_or(true)
+    }
+}
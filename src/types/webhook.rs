@@ -0,0 +1,91 @@
+use crate::{
+    database::model,
+    types::{prelude::*, KeyEvent},
+};
+
+/// A single pending POST of a [`KeyEvent`] to one webhook URL.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhookDelivery {
+    pub id: u64,
+    pub url: String,
+    pub key_event: KeyEvent,
+    pub attempts: u32,
+    /// Unix timestamp (seconds) before which the delivery is not retried.
+    pub next_attempt_at: u64,
+}
+
+/// Persisted queue of webhook deliveries so that events survive a restart
+/// while an endpoint is down.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WebhookQueue {
+    next_id: u64,
+    deliveries: Vec<WebhookDelivery>,
+}
+
+model!(WebhookQueue, key());
+
+impl WebhookQueue {
+    pub fn initialize() -> Result<(), KvStoreError> {
+        if Self::get().is_err() {
+            let webhook_queue = Self::default();
+
+            webhook_queue.put()?
+        }
+
+        Ok(())
+    }
+
+    pub fn push(&mut self, url: String, key_event: KeyEvent, now: u64) {
+        let delivery = WebhookDelivery {
+            id: self.next_id,
+            url,
+            key_event,
+            attempts: 0,
+            next_attempt_at: now,
+        };
+
+        self.next_id += 1;
+        self.deliveries.push(delivery);
+    }
+
+    /// Deliveries to `url` that are due at `now`, oldest first.
+    pub fn due_deliveries(&self, url: &str, now: u64) -> Vec<WebhookDelivery> {
+        self.deliveries
+            .iter()
+            .filter(|delivery| delivery.url == url && delivery.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// The URLs deliveries are queued for.
+    pub fn urls(&self) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for delivery in self.deliveries.iter() {
+            if !urls.contains(&delivery.url) {
+                urls.push(delivery.url.clone());
+            }
+        }
+
+        urls
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.deliveries.retain(|delivery| delivery.id != id);
+    }
+
+    pub fn reschedule(&mut self, id: u64, next_attempt_at: u64) -> Option<&WebhookDelivery> {
+        let delivery = self
+            .deliveries
+            .iter_mut()
+            .find(|delivery| delivery.id == id)?;
+
+        delivery.attempts += 1;
+        delivery.next_attempt_at = next_attempt_at;
+
+        Some(delivery)
+    }
+
+    pub fn len(&self) -> usize {
+        self.deliveries.len()
+    }
+}
@@ -0,0 +1,131 @@
+use std::{fs, path::Path};
+
+use distributed_key_generation::{
+    archive::{export_archive, import_archive, ArchiveError},
+    database,
+    types::{DecryptionKey, KeyId, LatestDecryptionKeyId},
+};
+use radius_sdk::kvstore::KvStore;
+use tempfile::TempDir;
+
+const DECRYPTION_KEY: &str = "0x1234";
+
+/// A fresh database of its own, so that the exporting and the importing node
+/// do not share state.
+fn open_database(directory: &TempDir) -> &'static KvStore {
+    let database = Box::leak(Box::new(database::open(directory.path()).unwrap()));
+    database::sync_scope(database, || database::initialize_models().unwrap());
+
+    database
+}
+
+/// Exports key ids `0..=4` of a node holding the decryption key of key id 2
+/// to `path`.
+fn export(path: &Path) {
+    let directory = TempDir::new().unwrap();
+
+    database::sync_scope(open_database(&directory), || {
+        KeyId::new(5).put().unwrap();
+        DecryptionKey::new(DECRYPTION_KEY.to_owned())
+            .put(KeyId::new(2))
+            .unwrap();
+        LatestDecryptionKeyId::advance(KeyId::new(2)).unwrap();
+
+        let summary = export_archive(path, KeyId::new(0), KeyId::new(4)).unwrap();
+        assert_eq!(summary.round_count, 1);
+        assert_eq!(summary.decryption_key_count, 1);
+    });
+}
+
+/// Imports `path` into a fresh database and checks that a failed import
+/// wrote nothing.
+fn import(path: &Path) -> Result<(), ArchiveError> {
+    let directory = TempDir::new().unwrap();
+
+    database::sync_scope(open_database(&directory), || {
+        let result = import_archive(path).map(|_| ());
+        if result.is_err() {
+            assert!(DecryptionKey::get(KeyId::new(2)).is_err());
+            assert_eq!(KeyId::get().unwrap(), KeyId::new(0));
+        }
+
+        result
+    })
+}
+
+fn edit_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>)) {
+    let mut lines: Vec<String> = fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect();
+    edit(&mut lines);
+
+    fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn exported_archive_imports_into_another_node() {
+    let directory = TempDir::new().unwrap();
+    let path = directory.path().join("keys.archive");
+    export(&path);
+
+    let database_directory = TempDir::new().unwrap();
+    database::sync_scope(open_database(&database_directory), || {
+        let summary = import_archive(&path).unwrap();
+
+        assert_eq!(summary.round_count, 1);
+        assert_eq!(summary.decryption_key_count, 1);
+        assert_eq!(KeyId::get().unwrap(), KeyId::new(5));
+        assert_eq!(
+            DecryptionKey::get(KeyId::new(2)).unwrap().as_string(),
+            DECRYPTION_KEY
+        );
+        assert_eq!(
+            LatestDecryptionKeyId::get().unwrap().key_id(),
+            KeyId::new(2)
+        );
+    });
+}
+
+#[test]
+fn edited_record_fails_the_checksum() {
+    let directory = TempDir::new().unwrap();
+    let path = directory.path().join("keys.archive");
+    export(&path);
+
+    edit_lines(&path, |lines| {
+        for line in lines.iter_mut() {
+            *line = line.replace(DECRYPTION_KEY, "0x5678");
+        }
+    });
+
+    assert!(matches!(import(&path), Err(ArchiveError::ChecksumMismatch)));
+}
+
+#[test]
+fn truncated_archive_is_rejected() {
+    let directory = TempDir::new().unwrap();
+    let path = directory.path().join("keys.archive");
+    export(&path);
+
+    edit_lines(&path, |lines| {
+        lines.pop();
+    });
+
+    assert!(matches!(import(&path), Err(ArchiveError::MissingFooter)));
+}
+
+#[test]
+fn data_after_the_footer_is_rejected() {
+    let directory = TempDir::new().unwrap();
+    let path = directory.path().join("keys.archive");
+    export(&path);
+
+    edit_lines(&path, |lines| {
+        let round = lines[3].clone();
+        lines.push(round);
+    });
+
+    assert!(matches!(import(&path), Err(ArchiveError::TrailingData)));
+}
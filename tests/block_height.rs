@@ -0,0 +1,88 @@
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use common::mock_http;
+use distributed_key_generation::{
+    chain::ChainClient,
+    task::scheduler::{BlockHeightScheduler, RoundScheduler},
+    types::BlockRange,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Local stand-in for a chain node that answers every request with
+/// `eth_blockNumber` semantics for the shared height.
+async fn mock_chain(block_number: Arc<AtomicU64>) -> String {
+    mock_http(move |_| {
+        (
+            "200 OK",
+            format!(
+                r#"{{"jsonrpc":"2.0","id":null,"result":"{:#x}"}}"#,
+                block_number.load(Ordering::Relaxed)
+            ),
+        )
+    })
+    .await
+}
+
+#[tokio::test]
+async fn chain_client_reads_block_number() {
+    let block_number = Arc::new(AtomicU64::new(0x1b4));
+    let chain_client = ChainClient::new(mock_chain(block_number).await).unwrap();
+
+    assert_eq!(chain_client.block_number().await.unwrap(), 0x1b4);
+}
+
+#[tokio::test]
+async fn round_starts_when_height_crosses_boundary() {
+    let block_number = Arc::new(AtomicU64::new(5));
+    let chain_rpc_url = mock_chain(block_number.clone()).await;
+    let mut scheduler = BlockHeightScheduler::new(chain_rpc_url, 10, POLL_INTERVAL);
+
+    // The range under way when the scheduler starts gets no round.
+    let no_round = tokio::time::timeout(Duration::from_millis(200), scheduler.next_round()).await;
+    assert!(no_round.is_err());
+
+    block_number.store(12, Ordering::Relaxed);
+    let round_start = tokio::time::timeout(Duration::from_secs(5), scheduler.next_round())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        round_start.block_range,
+        Some(BlockRange {
+            from_block: 10,
+            to_block: 19,
+        })
+    );
+}
+
+#[tokio::test]
+async fn every_skipped_range_starts_a_round() {
+    let block_number = Arc::new(AtomicU64::new(12));
+    let chain_rpc_url = mock_chain(block_number.clone()).await;
+    let mut scheduler = BlockHeightScheduler::new(chain_rpc_url, 10, POLL_INTERVAL);
+
+    let _ = tokio::time::timeout(Duration::from_millis(100), scheduler.next_round()).await;
+
+    block_number.store(35, Ordering::Relaxed);
+    for range_index in 2..=3 {
+        let round_start = tokio::time::timeout(Duration::from_secs(5), scheduler.next_round())
+            .await
+            .unwrap();
+        assert_eq!(
+            round_start.block_range,
+            Some(BlockRange::from_index(range_index, 10))
+        );
+    }
+
+    let no_round = tokio::time::timeout(Duration::from_millis(200), scheduler.next_round()).await;
+    assert!(no_round.is_err());
+}
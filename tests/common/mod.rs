@@ -0,0 +1,505 @@
+//! In-process multi-node test harness.
+//!
+//! Every node has its own temporary config directory and database and serves
+//! the real cluster RPC handlers on a [`SimulatedNetwork`]. Rounds only start
+//! when a test asks for one (the `external` round schedule), and the SKDE
+//! parameters are small enough to solve the time-lock puzzle at once, so
+//! tests run under a paused clock (`#[tokio::test(start_paused = true)]`)
+//! and finish without waiting in real time.
+
+#![allow(dead_code)]
+
+use std::{fs, future::Future, str::FromStr, sync::Arc, time::Duration};
+
+use clap::Parser;
+use distributed_key_generation::{
+    database,
+    rpc::{
+        cluster::{
+            GetKeyGeneratorList, Heartbeat, RunGeneratePartialKey, SyncAggregatedKey,
+            SyncAggregatedKeySignature, SyncKeyGenerator, SyncPartialKey,
+        },
+        internal::AddKeyGenerator,
+        prelude::{RpcError, RpcParameter},
+        transport::{self, FaultConfig, SimulatedHandler, SimulatedNetwork},
+    },
+    state::AppState,
+    task::{heartbeat::run_heartbeat, single_key_generator::run_single_key_generator},
+    types::*,
+};
+use radius_sdk::{
+    kvstore::KvStore,
+    signature::{Address, ChainType},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use skde::{delay_encryption::SkdeParams, setup, BigUint};
+use tempfile::TempDir;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Well-known development keys; the first one is the binary's default.
+const SIGNING_KEYS: [(&str, &str); 4] = [
+    (
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    ),
+    (
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    ),
+    (
+        "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+        "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+    ),
+    (
+        "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+        "0x90F79bf6EB2c4f870365E785982E1f101E93b906",
+    ),
+];
+
+/// The SKDE parameters of the binary, with a time parameter small enough to
+/// solve the puzzle at once.
+const PRIME_P: &str = "8155133734070055735139271277173718200941522166153710213522626777763679009805792017274916613411023848268056376687809186180768200590914945958831360737612803";
+const PRIME_Q: &str = "13379153270147861840625872456862185586039997603014979833900847304743997773803109864546170215161716700184487787472783869920830925415022501258643369350348243";
+const GENERATOR: &str = "4";
+const TIME: u32 = 4;
+
+const NETWORK_SEED: u64 = 7;
+const PARTIAL_KEY_AGGREGATION_CYCLE: u64 = 1;
+
+/// Virtual time, so waiting is free unless the condition never holds.
+pub const WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub fn skde_params() -> SkdeParams {
+    setup(
+        TIME,
+        BigUint::from_str(PRIME_P).unwrap(),
+        BigUint::from_str(PRIME_Q).unwrap(),
+        BigUint::from_str(GENERATOR).unwrap(),
+        BigUint::from(SIGNING_KEYS.len() as u32),
+    )
+}
+
+pub struct TestNode {
+    pub index: usize,
+    pub address: Address,
+    pub cluster_rpc_url: String,
+    pub context: AppState,
+    database: &'static KvStore,
+    _config_dir: TempDir,
+    _database_dir: TempDir,
+}
+
+impl TestNode {
+    /// Starts node `index` on `network`. Every node except the leader
+    /// (index 0) fetches the key generator list from `seed_cluster_rpc_url`,
+    /// as the binary does.
+    async fn start(
+        network: &SimulatedNetwork,
+        index: usize,
+        seed_cluster_rpc_url: Option<&str>,
+    ) -> Self {
+        let (signing_key, address) = SIGNING_KEYS[index];
+        let cluster_rpc_url = format!("http://node-{}", index);
+
+        let config_dir = TempDir::new().unwrap();
+        let path = config_dir.path().to_str().unwrap().to_owned();
+        ConfigPath::parse_from(["key-generator", "--path", &path])
+            .init()
+            .unwrap();
+        fs::write(config_dir.path().join(SIGNING_KEY), signing_key).unwrap();
+
+        let mut arguments = vec![
+            "key-generator".to_owned(),
+            "--path".to_owned(),
+            path,
+            "--cluster-rpc-url".to_owned(),
+            cluster_rpc_url.clone(),
+            "--round-schedule".to_owned(),
+            "external".to_owned(),
+            "--partial-key-aggregation-cycle".to_owned(),
+            PARTIAL_KEY_AGGREGATION_CYCLE.to_string(),
+        ];
+        if let Some(seed_cluster_rpc_url) = seed_cluster_rpc_url {
+            arguments.push("--seed-cluster-rpc-url".to_owned());
+            arguments.push(seed_cluster_rpc_url.to_owned());
+        }
+        let config = Config::load(&mut ConfigOption::parse_from(arguments)).unwrap();
+
+        let database_dir = TempDir::new().unwrap();
+        let database: &'static KvStore =
+            Box::leak(Box::new(database::open(database_dir.path()).unwrap()));
+
+        let transport = Arc::new(network.transport(cluster_rpc_url.clone()));
+        let context = AppState::new(config, skde_params(), transport).unwrap();
+
+        let node = Self {
+            index,
+            address: Address::from_str(ChainType::Ethereum, address).unwrap(),
+            cluster_rpc_url,
+            context,
+            database,
+            _config_dir: config_dir,
+            _database_dir: database_dir,
+        };
+
+        node.scope(async {
+            database::initialize_models().unwrap();
+
+            if let Some(seed_cluster_rpc_url) = seed_cluster_rpc_url {
+                let response = transport::request(
+                    node.context.cluster_transport(),
+                    seed_cluster_rpc_url,
+                    &GetKeyGeneratorList,
+                )
+                .await
+                .unwrap();
+
+                let key_generator_list =
+                    KeyGeneratorList::try_from(response.key_generator_rpc_url_list).unwrap();
+                key_generator_list.put().unwrap();
+            }
+
+            run_heartbeat(node.context.clone());
+        })
+        .await;
+
+        network.register(node.cluster_rpc_url.clone(), node.handler());
+
+        node
+    }
+
+    /// Runs `future` against the database of this node.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        database::scope(self.database, future).await
+    }
+
+    /// Calls an RPC handler of this node directly.
+    pub async fn call<P>(&self, parameter: P) -> Result<P::Response, RpcError>
+    where
+        P: RpcParameter<AppState>,
+    {
+        self.scope(parameter.handler(self.context.clone())).await
+    }
+
+    pub async fn aggregated_key(&self, key_id: KeyId) -> Option<String> {
+        self.scope(async { AggregatedKey::get(key_id).ok() })
+            .await
+            .map(AggregatedKey::encryption_key)
+    }
+
+    pub async fn decryption_key(&self, key_id: KeyId) -> Option<String> {
+        self.scope(async { DecryptionKey::get(key_id).ok() })
+            .await
+            .map(DecryptionKey::as_string)
+    }
+
+    /// Waits until this node has both keys of `key_id` and returns them.
+    pub async fn keys(&self, key_id: KeyId) -> (String, String) {
+        wait_for("both keys", || async {
+            self.decryption_key(key_id).await.is_some()
+        })
+        .await;
+
+        (
+            self.aggregated_key(key_id).await.unwrap(),
+            self.decryption_key(key_id).await.unwrap(),
+        )
+    }
+
+    /// Serves the cluster RPC methods of the binary.
+    fn handler(&self) -> SimulatedHandler {
+        let context = self.context.clone();
+        let database = self.database;
+
+        Arc::new(move |method, parameter| {
+            let context = context.clone();
+
+            Box::pin(database::scope(database, async move {
+                match method {
+                    "get_key_generator_list" => {
+                        handle::<GetKeyGeneratorList>(context, parameter).await
+                    }
+                    "heartbeat" => handle::<Heartbeat>(context, parameter).await,
+                    "sync_key_generator" => handle::<SyncKeyGenerator>(context, parameter).await,
+                    "sync_aggregated_key" => handle::<SyncAggregatedKey>(context, parameter).await,
+                    "sync_aggregated_key_signature" => {
+                        handle::<SyncAggregatedKeySignature>(context, parameter).await
+                    }
+                    "sync_partial_key" => handle::<SyncPartialKey>(context, parameter).await,
+                    "run_generate_partial_key" => {
+                        handle::<RunGeneratePartialKey>(context, parameter).await
+                    }
+                    _ => Err(format!("Method not found: {}", method)),
+                }
+            }))
+        })
+    }
+}
+
+async fn handle<P>(context: AppState, parameter: Value) -> Result<Value, String>
+where
+    P: RpcParameter<AppState> + DeserializeOwned,
+{
+    let parameter: P = serde_json::from_value(parameter).map_err(|error| error.to_string())?;
+    let response = parameter
+        .handler(context)
+        .await
+        .map_err(|error| format!("{:?}", error))?;
+
+    serde_json::to_value(response).map_err(|error| error.to_string())
+}
+
+pub struct TestCluster {
+    pub network: SimulatedNetwork,
+    pub nodes: Vec<TestNode>,
+}
+
+impl TestCluster {
+    /// Starts a leader and `node_count - 1` followers and registers all of
+    /// them as key generators.
+    pub async fn start(node_count: usize) -> Self {
+        let mut cluster = Self {
+            network: SimulatedNetwork::new(NETWORK_SEED, FaultConfig::default()),
+            nodes: Vec::new(),
+        };
+        for _ in 0..node_count {
+            cluster.add_node().await;
+        }
+
+        let leader = cluster.leader();
+        leader
+            .scope(async { run_single_key_generator(leader.context.clone()) })
+            .await;
+
+        cluster
+    }
+
+    pub fn leader(&self) -> &TestNode {
+        &self.nodes[0]
+    }
+
+    /// Starts one more node and registers it through the leader.
+    pub async fn add_node(&mut self) -> &TestNode {
+        let index = self.nodes.len();
+        let seed_cluster_rpc_url = self
+            .nodes
+            .first()
+            .map(|leader| leader.cluster_rpc_url.clone());
+
+        let node = TestNode::start(&self.network, index, seed_cluster_rpc_url.as_deref()).await;
+
+        let leader = self.nodes.first().unwrap_or(&node);
+        leader
+            .call(AddKeyGenerator::new(
+                node.address.clone(),
+                node.cluster_rpc_url.clone(),
+                format!("http://node-{}-external", index),
+            ))
+            .await
+            .expect("Failed to register key generator");
+
+        let cluster_rpc_url = node.cluster_rpc_url.clone();
+        self.nodes.push(node);
+
+        // Wait until every node knows the new one.
+        for node in self.nodes.iter() {
+            wait_for("key generator to be synced", || async {
+                node.scope(async {
+                    KeyGeneratorList::get()
+                        .unwrap()
+                        .get_all_key_generator_rpc_url_list()
+                        .contains(&cluster_rpc_url)
+                })
+                .await
+            })
+            .await;
+        }
+
+        self.nodes.last().unwrap()
+    }
+
+    /// Starts a round on the leader and returns its key id.
+    pub async fn trigger_round(&self) -> KeyId {
+        let leader = self.leader();
+        let key_id = leader.scope(async { KeyId::get().unwrap() }).await;
+
+        leader.context.round_trigger().notify_one();
+
+        key_id
+    }
+
+    /// Starts a round on the leader and waits until the leader has
+    /// aggregated its key.
+    pub async fn run_round(&self) -> KeyId {
+        let leader = self.leader();
+        let key_id = self.trigger_round().await;
+
+        wait_for("the leader to aggregate a key", || async {
+            leader.aggregated_key(key_id).await.is_some()
+        })
+        .await;
+
+        key_id
+    }
+
+    /// Takes node `index` off the network, as if it crashed.
+    pub fn drop_node(&self, index: usize) {
+        self.network.unregister(&self.nodes[index].cluster_rpc_url);
+    }
+
+    /// Puts node `index` back on the network with its state intact.
+    pub fn restore_node(&self, index: usize) {
+        let node = &self.nodes[index];
+        self.network
+            .register(node.cluster_rpc_url.clone(), node.handler());
+    }
+
+    /// Waits until every node considers every other node alive.
+    pub async fn wait_until_connected(&self) {
+        for node in self.nodes.iter() {
+            wait_for("every peer to be alive", || async {
+                node.scope(async {
+                    KeyGeneratorList::get()
+                        .unwrap()
+                        .get_all_alive_key_generator_rpc_url_list(&node.address)
+                        .len()
+                        == self.nodes.len()
+                })
+                .await
+            })
+            .await;
+        }
+    }
+}
+
+/// Polls `condition` until it holds, panicking after [`WAIT_TIMEOUT`].
+pub async fn wait_for<F, Fut>(description: &str, mut condition: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let result = tokio::time::timeout(WAIT_TIMEOUT, async {
+        while !condition().await {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await;
+
+    assert!(result.is_ok(), "Timed out waiting for {}", description);
+}
+
+/// A request received by a [`mock_http`] endpoint.
+pub struct MockRequest {
+    /// Request line and headers.
+    pub head: String,
+    pub body: String,
+}
+
+impl MockRequest {
+    /// The body parsed as JSON, or `Value::Null`.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or_default()
+    }
+}
+
+/// Local HTTP endpoint on a free port, e.g. a stand-in for a chain node.
+/// Answers every request with the status line and JSON body `respond`
+/// returns for it, and closes the connection.
+pub async fn mock_http<F>(respond: F) -> String
+where
+    F: Fn(&MockRequest) -> (&'static str, String) + Send + Sync + 'static,
+{
+    slow_mock_http(Duration::ZERO, respond).await
+}
+
+/// Like [`mock_http`], answering each request after `delay`.
+pub async fn slow_mock_http<F>(delay: Duration, respond: F) -> String
+where
+    F: Fn(&MockRequest) -> (&'static str, String) + Send + Sync + 'static,
+{
+    serve_mock_http(delay, "", respond).await
+}
+
+/// Like [`mock_http`], adding `headers` (e.g. `"Name: value\r\n"`) to every
+/// response.
+pub async fn mock_http_with_headers<F>(headers: &'static str, respond: F) -> String
+where
+    F: Fn(&MockRequest) -> (&'static str, String) + Send + Sync + 'static,
+{
+    serve_mock_http(Duration::ZERO, headers, respond).await
+}
+
+async fn serve_mock_http<F>(delay: Duration, headers: &'static str, respond: F) -> String
+where
+    F: Fn(&MockRequest) -> (&'static str, String) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let respond = Arc::new(respond);
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                continue;
+            };
+
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let Some(request) = read_request(&mut stream).await else {
+                    return;
+                };
+                let (status, body) = respond(&request);
+
+                tokio::time::sleep(delay).await;
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    url
+}
+
+/// Reads the head and the `Content-Length` bytes of body of one request.
+async fn read_request(stream: &mut TcpStream) -> Option<MockRequest> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+
+    loop {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buffer[..read]);
+
+        let Some(head_end) = request.windows(4).position(|window| window == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&request[..head_end]).into_owned();
+        let content_length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let body = &request[head_end + 4..];
+        if body.len() >= content_length {
+            let body = String::from_utf8_lossy(&body[..content_length]).into_owned();
+
+            return Some(MockRequest { head, body });
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use distributed_key_generation::{
+    database,
+    types::{DecryptionKey, KeyId},
+};
+use tempfile::TempDir;
+
+/// The models as earlier versions stored them, through the `Model` derive of
+/// radius_sdk.
+mod sdk {
+    use radius_sdk::kvstore::Model;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, Model)]
+    #[kvstore(key())]
+    pub struct KeyId(pub u64);
+
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Model)]
+    #[kvstore(key(key_id: super::KeyId))]
+    pub struct DecryptionKey(pub String);
+}
+
+#[test]
+fn records_of_the_sdk_derive_are_read_and_written_unchanged() {
+    let directory = TempDir::new().unwrap();
+    database::open(directory.path()).unwrap().init();
+
+    sdk::KeyId(5).put().unwrap();
+    sdk::DecryptionKey("0x1234".to_owned())
+        .put(KeyId::new(2))
+        .unwrap();
+
+    assert_eq!(KeyId::get().unwrap(), KeyId::new(5));
+    assert_eq!(
+        DecryptionKey::get(KeyId::new(2)).unwrap().as_string(),
+        "0x1234"
+    );
+
+    DecryptionKey::new("0x5678".to_owned())
+        .put(KeyId::new(3))
+        .unwrap();
+    assert_eq!(
+        sdk::DecryptionKey::get(KeyId::new(3)).unwrap(),
+        sdk::DecryptionKey("0x5678".to_owned())
+    );
+}
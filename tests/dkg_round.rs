@@ -0,0 +1,198 @@
+mod common;
+
+use common::{skde_params, wait_for, TestCluster, TestNode};
+use distributed_key_generation::{
+    rpc::cluster::{SyncAggregatedKey, SyncPartialKey},
+    task::round::aggregate_partial_keys,
+    types::{AggregatedKey, EncryptionKeyCertificate, KeyId, PartialKey},
+};
+use radius_sdk::signature::Address;
+use skde::{
+    delay_encryption::{decrypt, encrypt},
+    key_aggregation::aggregate_key,
+    key_generation::{
+        generate_partial_key, prove_partial_key_validity, PartialKey as SkdePartialKey,
+    },
+};
+
+#[tokio::test(start_paused = true)]
+async fn every_node_agrees_on_keys() {
+    let cluster = TestCluster::start(3).await;
+    let key_id = cluster.run_round().await;
+
+    let (encryption_key, decryption_key) = cluster.leader().keys(key_id).await;
+    for node in cluster.nodes.iter().skip(1) {
+        assert_eq!(
+            node.keys(key_id).await,
+            (encryption_key.clone(), decryption_key.clone()),
+            "node {} disagrees on key {:?}",
+            node.index,
+            key_id
+        );
+    }
+
+    let skde_params = skde_params();
+    let ciphertext = encrypt(&skde_params, "plaintext", &encryption_key).unwrap();
+    assert_eq!(
+        decrypt(&skde_params, &ciphertext, &decryption_key).unwrap(),
+        "plaintext"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn every_node_certifies_the_aggregated_key() {
+    let cluster = TestCluster::start(3).await;
+    let key_id = cluster.run_round().await;
+
+    // The leader signs first, so followers receive its signature before they
+    // have aggregated the key themselves.
+    for node in cluster.nodes.iter() {
+        wait_for("every signature", || async {
+            node.scope(async { EncryptionKeyCertificate::get(key_id) })
+                .await
+                .is_ok_and(|certificate| certificate.key_signatures.len() == cluster.nodes.len())
+        })
+        .await;
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn late_joining_node_receives_later_keys() {
+    let mut cluster = TestCluster::start(1).await;
+    let early_key_id = cluster.run_round().await;
+
+    cluster.add_node().await;
+    let key_id = cluster.run_round().await;
+
+    let late_node = &cluster.nodes[1];
+    assert_eq!(
+        late_node.keys(key_id).await,
+        cluster.leader().keys(key_id).await
+    );
+    assert_eq!(late_node.aggregated_key(early_key_id).await, None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn rounds_continue_after_node_drops() {
+    let cluster = TestCluster::start(2).await;
+    cluster.run_round().await;
+
+    cluster.drop_node(1);
+
+    let key_id = cluster.run_round().await;
+    cluster.leader().keys(key_id).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn invalid_partial_key_proof_is_rejected() {
+    let cluster = TestCluster::start(2).await;
+    let leader = cluster.leader();
+    let follower = &cluster.nodes[1];
+    let key_id = KeyId::new(1000);
+    let skde_params = skde_params();
+
+    // Pair a partial key with the proof of another secret value.
+    let (_, skde_partial_key) = generate_partial_key(&skde_params);
+    let (other_secret_value, _) = generate_partial_key(&skde_params);
+    follower
+        .call(SyncPartialKey {
+            address: leader.address.clone(),
+            key_id,
+            skde_partial_key,
+            partial_key_proof: prove_partial_key_validity(&skde_params, &other_secret_value),
+        })
+        .await
+        .unwrap();
+
+    let (secret_value, skde_partial_key) = generate_partial_key(&skde_params);
+    follower
+        .call(SyncPartialKey {
+            address: follower.address.clone(),
+            key_id,
+            skde_partial_key,
+            partial_key_proof: prove_partial_key_validity(&skde_params, &secret_value),
+        })
+        .await
+        .unwrap();
+
+    let (_, participant_addresses) = follower
+        .scope(async { aggregate_partial_keys(&follower.context, key_id) })
+        .await
+        .unwrap();
+
+    assert_eq!(participant_addresses, vec![follower.address.clone()]);
+    assert!(follower
+        .scope(async { PartialKey::get(key_id, &leader.address) })
+        .await
+        .is_err());
+}
+
+/// Hands `node` a valid partial key of `address` for `key_id` and returns it.
+async fn sync_partial_key(node: &TestNode, address: &Address, key_id: KeyId) -> SkdePartialKey {
+    let skde_params = skde_params();
+    let (secret_value, skde_partial_key) = generate_partial_key(&skde_params);
+    node.call(SyncPartialKey {
+        address: address.clone(),
+        key_id,
+        skde_partial_key: skde_partial_key.clone(),
+        partial_key_proof: prove_partial_key_validity(&skde_params, &secret_value),
+    })
+    .await
+    .unwrap();
+
+    skde_partial_key
+}
+
+#[tokio::test(start_paused = true)]
+async fn follower_aggregates_the_leaders_participants_only() {
+    let cluster = TestCluster::start(2).await;
+    let leader = cluster.leader();
+    let follower = &cluster.nodes[1];
+    let key_id = KeyId::new(1001);
+
+    // The follower also holds a partial key the leader did not aggregate.
+    let leader_partial_key = sync_partial_key(follower, &leader.address, key_id).await;
+    sync_partial_key(follower, &follower.address, key_id).await;
+
+    let aggregated_key = aggregate_key(&skde_params(), &vec![leader_partial_key]);
+    follower
+        .call(SyncAggregatedKey {
+            key_id,
+            aggregated_key: aggregated_key.clone(),
+            participant_addresses: vec![leader.address.clone()],
+            block_range: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        follower.aggregated_key(key_id).await,
+        Some(aggregated_key.u)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn aggregated_key_the_participants_do_not_give_is_rejected() {
+    let cluster = TestCluster::start(2).await;
+    let leader = cluster.leader();
+    let follower = &cluster.nodes[1];
+    let key_id = KeyId::new(1002);
+
+    sync_partial_key(follower, &leader.address, key_id).await;
+    let other_partial_key = sync_partial_key(follower, &follower.address, key_id).await;
+
+    let result = follower
+        .call(SyncAggregatedKey {
+            key_id,
+            aggregated_key: aggregate_key(&skde_params(), &vec![other_partial_key]),
+            participant_addresses: vec![leader.address.clone()],
+            block_range: None,
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert!(follower
+        .scope(async { AggregatedKey::get(key_id) })
+        .await
+        .is_err());
+}
@@ -0,0 +1,319 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use alloy_sol_types::SolCall;
+use common::mock_http;
+use distributed_key_generation::{
+    database,
+    publisher::{
+        encode_key_commitment, publishKeyCommitmentCall, EthereumKeyPublisher, FileKeyPublisher,
+        HttpKeyPublisher, KeyPublisher, PublishError,
+    },
+    types::{key_hash, CommitmentTransaction, KeyCommitment, KeyId, PublishState, PublishStatus},
+};
+use radius_sdk::{kvstore::KvStore, signature::Address};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+const TRANSACTION_HASH: &str = "0x8f7a2b1c0d9e8f7a2b1c0d9e8f7a2b1c0d9e8f7a2b1c0d9e8f7a2b1c0d9e8f7a";
+const CONTRACT_ADDRESS: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+/// Account of `SIGNING_KEY`.
+const ACCOUNT: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+/// A fresh database, where the Ethereum publisher keeps its transactions.
+fn open_database(directory: &TempDir) -> &'static KvStore {
+    Box::leak(Box::new(database::open(directory.path()).unwrap()))
+}
+
+fn key_commitment(key_id: u64) -> KeyCommitment {
+    KeyCommitment::new(
+        KeyId::new(key_id),
+        "1234567890",
+        vec![Address::from(vec![0x22; 20]), Address::from(vec![0x11; 20])],
+    )
+}
+
+/// Local endpoint that answers every request with `status` and `body` and
+/// keeps the last request around.
+async fn mock_endpoint(
+    status: &'static str,
+    body: String,
+    last_request: Arc<Mutex<String>>,
+) -> String {
+    mock_http(move |request| {
+        *last_request.lock().unwrap() = format!("{}\r\n\r\n{}", request.head, request.body);
+
+        (status, body.clone())
+    })
+    .await
+}
+
+#[test]
+fn commitment_is_deterministic() {
+    let key_commitment = key_commitment(3);
+
+    assert_eq!(key_commitment.key_hash, key_hash("1234567890"));
+    assert_eq!(key_commitment.key_hash.len(), 66);
+    assert_eq!(
+        key_commitment.participants,
+        vec![Address::from(vec![0x11; 20]), Address::from(vec![0x22; 20])]
+    );
+}
+
+#[tokio::test]
+async fn file_publisher_appends_one_line_per_commitment() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("commitments.jsonl");
+    let key_publisher = FileKeyPublisher::new(path.clone());
+
+    key_publisher.publish(&key_commitment(1)).await.unwrap();
+    key_publisher.publish(&key_commitment(2)).await.unwrap();
+
+    let lines: Vec<KeyCommitment> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines, vec![key_commitment(1), key_commitment(2)]);
+}
+
+#[tokio::test]
+async fn http_publisher_posts_the_commitment() {
+    let last_request = Arc::new(Mutex::new(String::new()));
+    let url = mock_endpoint("200 OK", String::new(), last_request.clone()).await;
+    let key_publisher = HttpKeyPublisher::new(url).unwrap();
+
+    key_publisher.publish(&key_commitment(4)).await.unwrap();
+
+    let last_request = last_request.lock().unwrap().clone();
+    assert!(last_request.starts_with("POST"));
+    assert!(last_request.contains(&key_commitment(4).key_hash));
+}
+
+#[tokio::test]
+async fn http_publisher_fails_on_error_status() {
+    let url = mock_endpoint("503 Service Unavailable", String::new(), Arc::default()).await;
+    let key_publisher = HttpKeyPublisher::new(url).unwrap();
+
+    assert!(key_publisher.publish(&key_commitment(4)).await.is_err());
+}
+
+/// Local stand-in for a chain node. Answers each JSON-RPC request with
+/// `result(method, call)`, where `call` counts the earlier requests for the
+/// same method, and keeps every request body around.
+async fn mock_chain(
+    result: impl Fn(&str, usize) -> Value + Send + Sync + 'static,
+    requests: Arc<Mutex<Vec<Value>>>,
+) -> String {
+    mock_http(move |request| {
+        let body = request.json();
+        let method = body["method"].as_str().unwrap_or_default().to_owned();
+        let call = {
+            let mut requests = requests.lock().unwrap();
+            let call = requests
+                .iter()
+                .filter(|request| request["method"] == method.as_str())
+                .count();
+            requests.push(body);
+
+            call
+        };
+
+        (
+            "200 OK",
+            json!({ "jsonrpc": "2.0", "id": null, "result": result(&method, call) }).to_string(),
+        )
+    })
+    .await
+}
+
+/// Answers of a chain node on which the commitment transaction is mined with
+/// `receipt_status`.
+fn chain_result(method: &str, receipt_status: &'static str) -> Value {
+    match method {
+        "eth_chainId" => json!("0x7a69"),
+        "eth_getTransactionCount" => json!("0x5"),
+        "eth_gasPrice" => json!("0x3b9aca00"),
+        "eth_estimateGas" => json!("0x186a0"),
+        "eth_sendRawTransaction" => json!(TRANSACTION_HASH),
+        "eth_getTransactionReceipt" => json!({ "status": receipt_status }),
+        _ => Value::Null,
+    }
+}
+
+fn ethereum_publisher(chain_rpc_url: String) -> EthereumKeyPublisher {
+    EthereumKeyPublisher::new(chain_rpc_url, CONTRACT_ADDRESS.to_owned(), SIGNING_KEY).unwrap()
+}
+
+fn requests_of<'a>(requests: &'a [Value], method: &'a str) -> impl Iterator<Item = &'a Value> {
+    requests
+        .iter()
+        .filter(move |request| request["method"] == method)
+}
+
+#[tokio::test]
+async fn ethereum_publisher_signs_the_transaction_itself() {
+    let directory = tempfile::tempdir().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let chain_rpc_url = mock_chain(|method, _| chain_result(method, "0x1"), requests.clone()).await;
+
+    database::scope(open_database(&directory), async {
+        let receipt = ethereum_publisher(chain_rpc_url)
+            .publish(&key_commitment(5))
+            .await
+            .unwrap();
+        assert_eq!(receipt.len(), 66);
+        assert!(CommitmentTransaction::get(KeyId::new(5)).is_err());
+    })
+    .await;
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests_of(&requests, "eth_sendTransaction").count(), 0);
+    assert_eq!(requests_of(&requests, "eth_sendRawTransaction").count(), 1);
+    assert!(
+        requests_of(&requests, "eth_estimateGas").all(|request| request["params"][0]["from"]
+            .as_str()
+            .is_some_and(|from| from.eq_ignore_ascii_case(ACCOUNT)))
+    );
+}
+
+#[tokio::test]
+async fn ethereum_publisher_resends_the_same_transaction() {
+    let directory = tempfile::tempdir().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    // The receipt of the first attempt cannot be read.
+    let chain_rpc_url = mock_chain(
+        |method, call| match (method, call) {
+            ("eth_getTransactionReceipt", 0) => json!("not a receipt"),
+            _ => chain_result(method, "0x1"),
+        },
+        requests.clone(),
+    )
+    .await;
+
+    database::scope(open_database(&directory), async {
+        let key_publisher = ethereum_publisher(chain_rpc_url);
+
+        assert!(key_publisher.publish(&key_commitment(6)).await.is_err());
+        key_publisher.publish(&key_commitment(6)).await.unwrap();
+    })
+    .await;
+
+    let requests = requests.lock().unwrap();
+    let raw_transactions: Vec<&Value> = requests_of(&requests, "eth_sendRawTransaction")
+        .map(|request| &request["params"][0])
+        .collect();
+    assert_eq!(raw_transactions.len(), 2);
+    assert_eq!(raw_transactions[0], raw_transactions[1]);
+    assert_eq!(requests_of(&requests, "eth_getTransactionCount").count(), 1);
+}
+
+#[tokio::test]
+async fn reverted_commitment_is_not_published() {
+    let directory = tempfile::tempdir().unwrap();
+    let chain_rpc_url = mock_chain(|method, _| chain_result(method, "0x0"), Arc::default()).await;
+
+    database::scope(open_database(&directory), async {
+        let result = ethereum_publisher(chain_rpc_url)
+            .publish(&key_commitment(7))
+            .await;
+
+        assert!(matches!(result, Err(PublishError::Reverted(_))));
+        // The next attempt signs a new transaction.
+        assert!(CommitmentTransaction::get(KeyId::new(7)).is_err());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn stuck_commitment_is_replaced_with_a_higher_gas_price() {
+    let directory = tempfile::tempdir().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    // The transaction is never mined and its nonce never taken.
+    let chain_rpc_url = mock_chain(
+        |method, _| match method {
+            "eth_getTransactionReceipt" => Value::Null,
+            _ => chain_result(method, "0x1"),
+        },
+        requests.clone(),
+    )
+    .await;
+
+    database::scope(open_database(&directory), async {
+        let key_publisher =
+            ethereum_publisher(chain_rpc_url).with_receipt_polling(Duration::ZERO, 1);
+
+        for _ in 0..3 {
+            let result = key_publisher.publish(&key_commitment(8)).await;
+            assert!(matches!(result, Err(PublishError::NotMined(_))));
+        }
+
+        let replacement = CommitmentTransaction::get(KeyId::new(8)).unwrap();
+        assert_eq!(replacement.nonce, 5);
+        assert!(replacement.gas_price > 0x3b9aca00);
+        assert_eq!(replacement.replaced_hashes.len(), 1);
+
+        assert!(key_publisher.publish(&key_commitment(8)).await.is_err());
+        key_publisher.abandon(&key_commitment(8)).unwrap();
+        assert!(CommitmentTransaction::get(KeyId::new(8)).is_err());
+    })
+    .await;
+
+    let requests = requests.lock().unwrap();
+    let raw_transactions: Vec<&Value> = requests_of(&requests, "eth_sendRawTransaction")
+        .map(|request| &request["params"][0])
+        .collect();
+    assert_eq!(raw_transactions.len(), 4);
+    assert_eq!(raw_transactions[0], raw_transactions[2]);
+    assert_ne!(raw_transactions[2], raw_transactions[3]);
+    // Both the replaced and the new transaction are looked for.
+    assert_eq!(
+        requests_of(&requests, "eth_getTransactionReceipt").count(),
+        5
+    );
+}
+
+#[test]
+fn calldata_carries_the_commitment() {
+    let calldata = encode_key_commitment(&key_commitment(6)).unwrap();
+    let call = publishKeyCommitmentCall::abi_decode(&calldata, true).unwrap();
+
+    assert_eq!(call.keyId, 6);
+    assert_eq!(
+        const_hex::encode_prefixed(call.keyHash),
+        key_commitment(6).key_hash
+    );
+    assert_eq!(call.participants.len(), 2);
+}
+
+#[test]
+fn publish_status_gives_up_after_max_attempts() {
+    let mut publish_status = PublishStatus::new(key_commitment(7), 100);
+    assert!(publish_status.is_due(100));
+
+    publish_status.record_failure("unreachable".to_owned(), 100, 2, 2);
+    assert_eq!(publish_status.state, PublishState::Pending);
+    assert!(!publish_status.is_due(101));
+    assert!(publish_status.is_due(102));
+
+    publish_status.record_failure("unreachable".to_owned(), 102, 4, 2);
+    assert_eq!(publish_status.state, PublishState::Failed);
+    assert!(!publish_status.is_due(200));
+}
+
+#[test]
+fn publish_status_keeps_the_receipt() {
+    let mut publish_status = PublishStatus::new(key_commitment(8), 100);
+    publish_status.record_failure("unreachable".to_owned(), 100, 1, 10);
+    publish_status.record_success(TRANSACTION_HASH.to_owned());
+
+    assert_eq!(publish_status.state, PublishState::Published);
+    assert_eq!(publish_status.attempts, 2);
+    assert_eq!(publish_status.receipt.as_deref(), Some(TRANSACTION_HASH));
+    assert_eq!(publish_status.last_error, None);
+}
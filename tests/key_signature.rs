@@ -0,0 +1,95 @@
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+};
+
+use distributed_key_generation::{
+    database,
+    types::{
+        EncryptionKeyCertificate, KeyId, KeySignature, KeySignatureMessage, PendingKeySignatures,
+    },
+};
+use radius_sdk::{
+    kvstore::KvStore,
+    signature::{ChainType, PrivateKeySigner},
+};
+use tempfile::TempDir;
+
+/// Well-known development keys.
+const SIGNING_KEYS: [&str; 4] = [
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+];
+
+fn open_database(directory: &TempDir) -> &'static KvStore {
+    Box::leak(Box::new(database::open(directory.path()).unwrap()))
+}
+
+fn message() -> KeySignatureMessage {
+    KeySignatureMessage::new(KeyId::new(1), "0x1234".to_owned(), "hash".to_owned())
+}
+
+fn key_signature(signing_key: &str) -> KeySignature {
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+    KeySignature::sign(&signer, &message()).unwrap()
+}
+
+/// Runs `operation` once per signing key, all at the same time.
+fn concurrently(database: &'static KvStore, operation: fn(KeySignature)) {
+    let barrier = Arc::new(Barrier::new(SIGNING_KEYS.len()));
+    let threads: Vec<_> = SIGNING_KEYS
+        .iter()
+        .map(|signing_key| {
+            let barrier = barrier.clone();
+            let key_signature = key_signature(signing_key);
+
+            thread::spawn(move || {
+                database::sync_scope(database, || {
+                    barrier.wait();
+                    operation(key_signature);
+                })
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[test]
+fn concurrent_first_signatures_are_all_kept() {
+    let directory = TempDir::new().unwrap();
+    let database = open_database(&directory);
+
+    concurrently(database, |key_signature| {
+        EncryptionKeyCertificate::add_signature(&message(), key_signature).unwrap();
+    });
+
+    let certificate =
+        database::sync_scope(database, || EncryptionKeyCertificate::get(KeyId::new(1))).unwrap();
+    assert_eq!(certificate.key_signatures.len(), SIGNING_KEYS.len());
+}
+
+#[test]
+fn concurrent_pending_signatures_are_all_kept() {
+    let directory = TempDir::new().unwrap();
+    let database = open_database(&directory);
+
+    concurrently(database, |key_signature| {
+        PendingKeySignatures::push(KeyId::new(1), key_signature).unwrap();
+    });
+
+    database::sync_scope(database, || {
+        assert_eq!(
+            PendingKeySignatures::take(KeyId::new(1)).unwrap().len(),
+            SIGNING_KEYS.len()
+        );
+        assert!(PendingKeySignatures::take(KeyId::new(1))
+            .unwrap()
+            .is_empty());
+    });
+}
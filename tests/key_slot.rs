@@ -0,0 +1,52 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use distributed_key_generation::{
+    database,
+    types::{KeyId, KeySlotClock, LatestAggregatedKeyId},
+};
+use tempfile::TempDir;
+
+const GENESIS_TIME: u64 = 1_000;
+const CYCLE: u64 = 10;
+
+fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[test]
+fn key_id_is_the_slot_since_genesis() {
+    let key_slot_clock = KeySlotClock::new(GENESIS_TIME, CYCLE);
+
+    assert_eq!(key_slot_clock.key_id_at(at(1_000)), Some(KeyId::new(0)));
+    assert_eq!(key_slot_clock.key_id_at(at(1_009)), Some(KeyId::new(0)));
+    assert_eq!(key_slot_clock.key_id_at(at(1_010)), Some(KeyId::new(1)));
+    assert_eq!(key_slot_clock.key_id_at(at(1_255)), Some(KeyId::new(25)));
+}
+
+#[test]
+fn there_is_no_key_id_before_genesis() {
+    let key_slot_clock = KeySlotClock::new(GENESIS_TIME, CYCLE);
+
+    assert_eq!(key_slot_clock.key_id_at(at(999)), None);
+}
+
+#[test]
+fn zero_cycle_is_clamped_to_a_second() {
+    let key_slot_clock = KeySlotClock::new(GENESIS_TIME, 0);
+
+    assert_eq!(key_slot_clock.cycle, 1);
+    assert_eq!(key_slot_clock.key_id_at(at(1_003)), Some(KeyId::new(3)));
+}
+
+#[test]
+fn latest_aggregated_key_id_initializes_quickly_from_a_slot_key_id() {
+    let directory = TempDir::new().unwrap();
+    let database = Box::leak(Box::new(database::open(directory.path()).unwrap()));
+
+    database::sync_scope(database, || {
+        KeyId::new(u64::MAX / 2).put().unwrap();
+        LatestAggregatedKeyId::initialize().unwrap();
+
+        assert!(LatestAggregatedKeyId::get().is_err());
+    });
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use alloy_sol_types::SolCall;
+use common::mock_http;
+use distributed_key_generation::{
+    chain::ChainClient,
+    registry::{
+        decode_registered_key_generators, fetch_registered_key_generators, getKeyGeneratorsCall,
+        RegisteredKeyGenerator,
+    },
+    types::{KeyGenerator, KeyGeneratorList},
+};
+use radius_sdk::signature::Address;
+
+const CONTRACT_ADDRESS: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+
+fn registered(byte: u8, port: u16) -> RegisteredKeyGenerator {
+    RegisteredKeyGenerator {
+        keyGenerator: alloy_primitives::Address::repeat_byte(byte),
+        clusterRpcUrl: format!("http://127.0.0.1:{}", port),
+        externalRpcUrl: format!("http://127.0.0.1:{}", port + 1),
+    }
+}
+
+fn key_generator(byte: u8, port: u16) -> KeyGenerator {
+    KeyGenerator::new(
+        Address::from(vec![byte; 20]),
+        format!("http://127.0.0.1:{}", port),
+        format!("http://127.0.0.1:{}", port + 1),
+    )
+}
+
+/// Local stand-in for a chain node that answers every `eth_call` with the
+/// ABI encoded `registered` set and keeps the last request body around.
+async fn mock_registry(
+    registered: Vec<RegisteredKeyGenerator>,
+    last_request: Arc<Mutex<String>>,
+) -> String {
+    let return_data =
+        const_hex::encode_prefixed(getKeyGeneratorsCall::abi_encode_returns(&(registered,)));
+
+    mock_http(move |request| {
+        *last_request.lock().unwrap() = request.body.clone();
+
+        (
+            "200 OK",
+            format!(
+                r#"{{"jsonrpc":"2.0","id":null,"result":"{}"}}"#,
+                return_data
+            ),
+        )
+    })
+    .await
+}
+
+#[tokio::test]
+async fn registry_is_read_at_the_given_block() {
+    let last_request = Arc::new(Mutex::new(String::new()));
+    let chain_rpc_url = mock_registry(
+        vec![registered(0x11, 5000), registered(0x22, 5010)],
+        last_request.clone(),
+    )
+    .await;
+    let chain_client = ChainClient::new(chain_rpc_url).unwrap();
+
+    let key_generators = fetch_registered_key_generators(&chain_client, CONTRACT_ADDRESS, Some(42))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        key_generators,
+        vec![key_generator(0x11, 5000), key_generator(0x22, 5010)]
+    );
+
+    let last_request = last_request.lock().unwrap().clone();
+    assert!(last_request.contains(r#""method":"eth_call""#));
+    assert!(last_request.contains(CONTRACT_ADDRESS));
+    assert!(last_request.contains(r#""0x2a""#));
+}
+
+#[tokio::test]
+async fn empty_registry_is_decoded() {
+    let chain_rpc_url = mock_registry(Vec::new(), Arc::default()).await;
+    let chain_client = ChainClient::new(chain_rpc_url).unwrap();
+
+    let key_generators = fetch_registered_key_generators(&chain_client, CONTRACT_ADDRESS, None)
+        .await
+        .unwrap();
+
+    assert!(key_generators.is_empty());
+}
+
+#[test]
+fn malformed_return_data_is_rejected() {
+    assert!(decode_registered_key_generators(&[0u8; 7]).is_err());
+}
+
+#[test]
+fn reconcile_reports_joined_and_left_key_generators() {
+    let mut key_generator_list = KeyGeneratorList::default();
+    key_generator_list.insert(key_generator(0x11, 5000));
+    key_generator_list.insert(key_generator(0x22, 5010));
+
+    let membership_change =
+        key_generator_list.reconcile(vec![key_generator(0x22, 5010), key_generator(0x33, 5020)]);
+
+    assert_eq!(membership_change.added, vec![key_generator(0x33, 5020)]);
+    assert_eq!(membership_change.removed, vec![key_generator(0x11, 5000)]);
+    assert!(key_generator_list.contains(&key_generator(0x33, 5020)));
+    assert!(!key_generator_list.contains(&key_generator(0x11, 5000)));
+
+    let membership_change =
+        key_generator_list.reconcile(vec![key_generator(0x33, 5020), key_generator(0x22, 5010)]);
+    assert!(membership_change.is_empty());
+}
+
+#[test]
+fn moved_key_generator_is_replaced() {
+    let mut key_generator_list = KeyGeneratorList::default();
+    key_generator_list.insert(key_generator(0x11, 5000));
+
+    let membership_change = key_generator_list.reconcile(vec![key_generator(0x11, 6000)]);
+
+    assert_eq!(membership_change.added, vec![key_generator(0x11, 6000)]);
+    assert_eq!(membership_change.removed, vec![key_generator(0x11, 5000)]);
+}
@@ -0,0 +1,333 @@
+mod common;
+
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::{Duration, Instant},
+};
+
+use common::{mock_http_with_headers, slow_mock_http};
+use distributed_key_generation::{
+    rpc::{run_rpc_gateway, RateLimiter},
+    types::RpcLimits,
+};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const UPSTREAM_RESPONSE: &str = r#"{"jsonrpc":"2.0","result":"ok","id":1}"#;
+
+fn limits() -> RpcLimits {
+    RpcLimits {
+        rate_limit_per_second: None,
+        rate_limit_burst: 0,
+        max_body_size: 1024,
+        max_concurrent_requests: 16,
+        max_connections_per_ip: 16,
+        api_keys: Vec::new(),
+    }
+}
+
+/// Stand-in for the RPC server behind the gateway. Answers every request
+/// after `delay`.
+async fn mock_upstream(delay: Duration) -> String {
+    slow_mock_http(delay, |_| ("200 OK", UPSTREAM_RESPONSE.to_owned())).await
+}
+
+async fn gateway(limits: RpcLimits, delay: Duration) -> String {
+    let bound_address = run_rpc_gateway(
+        "127.0.0.1:0".to_owned(),
+        mock_upstream(delay).await,
+        limits,
+        None,
+    )
+    .await
+    .unwrap();
+
+    format!("http://{}", bound_address)
+}
+
+fn request() -> Value {
+    json!({ "jsonrpc": "2.0", "method": "get_latest_key_id", "params": {}, "id": 1 })
+}
+
+async fn error_code(response: reqwest::Response) -> i64 {
+    let body: Value = response.json().await.unwrap();
+
+    body["error"]["code"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn accepted_request_is_forwarded() {
+    let url = gateway(limits(), Duration::ZERO).await;
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), UPSTREAM_RESPONSE);
+}
+
+#[tokio::test]
+async fn oversized_body_is_rejected() {
+    let url = gateway(limits(), Duration::ZERO).await;
+    let body = json!({ "jsonrpc": "2.0", "method": "decrypt", "params": { "ciphertext": "a".repeat(2048) }, "id": 1 });
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(error_code(response).await, -32600);
+}
+
+#[tokio::test]
+async fn unknown_api_key_is_rejected() {
+    let url = gateway(
+        RpcLimits {
+            api_keys: vec!["secret".to_owned()],
+            ..limits()
+        },
+        Duration::ZERO,
+    )
+    .await;
+    let http_client = reqwest::Client::new();
+
+    let response = http_client
+        .post(&url)
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(error_code(response).await, -32001);
+
+    let response = http_client
+        .post(&url)
+        .header("x-api-key", "wrong")
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth("secret")
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn requests_beyond_the_burst_are_rate_limited() {
+    let url = gateway(
+        RpcLimits {
+            rate_limit_per_second: Some(1),
+            rate_limit_burst: 2,
+            ..limits()
+        },
+        Duration::ZERO,
+    )
+    .await;
+    let http_client = reqwest::Client::new();
+
+    for _ in 0..2 {
+        let response = http_client
+            .post(&url)
+            .json(&request())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = http_client
+        .post(&url)
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(error_code(response).await, -32005);
+}
+
+#[tokio::test]
+async fn requests_beyond_the_concurrency_limit_are_rejected() {
+    let url = gateway(
+        RpcLimits {
+            max_concurrent_requests: 1,
+            ..limits()
+        },
+        Duration::from_millis(500),
+    )
+    .await;
+
+    let slow_request = {
+        let url = url.clone();
+        tokio::spawn(async move {
+            reqwest::Client::new()
+                .post(&url)
+                .json(&request())
+                .send()
+                .await
+                .unwrap()
+                .status()
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    assert_eq!(slow_request.await.unwrap(), StatusCode::OK);
+}
+
+#[tokio::test(start_paused = true)]
+async fn slow_body_is_cut_off() {
+    let url = gateway(limits(), Duration::ZERO).await;
+    let mut stream = TcpStream::connect(url.trim_start_matches("http://"))
+        .await
+        .unwrap();
+
+    // Announce a body and never send it.
+    stream
+        .write_all(b"POST / HTTP/1.1\r\nHost: gateway\r\nContent-Length: 64\r\n\r\n{")
+        .await
+        .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 408"));
+}
+
+#[tokio::test]
+async fn connections_beyond_the_per_ip_limit_are_closed() {
+    let url = gateway(
+        RpcLimits {
+            max_connections_per_ip: 1,
+            ..limits()
+        },
+        Duration::ZERO,
+    )
+    .await;
+    let address = url.trim_start_matches("http://");
+
+    let _open_connection = TcpStream::connect(address).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    assert!(response.is_empty());
+}
+
+#[tokio::test]
+async fn duplicate_content_lengths_are_rejected() {
+    let url = gateway(limits(), Duration::ZERO).await;
+    let mut stream = TcpStream::connect(url.trim_start_matches("http://"))
+        .await
+        .unwrap();
+
+    stream
+        .write_all(
+            b"POST / HTTP/1.1\r\nHost: gateway\r\nContent-Length: 2\r\nContent-Length: 64\r\n\r\n{}",
+        )
+        .await
+        .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 400"));
+}
+
+#[tokio::test]
+async fn cors_preflight_and_upstream_headers_are_forwarded() {
+    // Answers preflights that carry the CORS request headers with 204.
+    let upstream = mock_http_with_headers("Access-Control-Allow-Origin: *\r\n", |request| {
+        let head = request.head.to_ascii_lowercase();
+        if head.starts_with("options")
+            && head.contains("origin: https://app.example")
+            && head.contains("access-control-request-method: post")
+        {
+            ("204 No Content", String::new())
+        } else {
+            ("200 OK", UPSTREAM_RESPONSE.to_owned())
+        }
+    })
+    .await;
+    let bound_address = run_rpc_gateway(
+        "127.0.0.1:0".to_owned(),
+        upstream,
+        RpcLimits {
+            api_keys: vec!["secret".to_owned()],
+            ..limits()
+        },
+        None,
+    )
+    .await
+    .unwrap();
+    let url = format!("http://{}", bound_address);
+    let client = reqwest::Client::new();
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("Origin", "https://app.example")
+        .header("Access-Control-Request-Method", "POST")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.status(), StatusCode::NO_CONTENT);
+    assert_eq!(preflight.headers()["access-control-allow-origin"], "*");
+
+    let response = client
+        .post(&url)
+        .header("x-api-key", "secret")
+        .json(&request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+}
+
+#[tokio::test]
+async fn other_methods_are_not_served() {
+    let url = gateway(limits(), Duration::ZERO).await;
+
+    let response = reqwest::get(&url).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[test]
+fn rate_limiter_refills_over_time() {
+    let rate_limiter = RateLimiter::new(2, 2);
+    let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+    let now = Instant::now();
+
+    assert!(rate_limiter.check(ip, now));
+    assert!(rate_limiter.check(ip, now));
+    assert!(!rate_limiter.check(ip, now));
+    assert!(rate_limiter.check(other_ip, now));
+
+    assert!(rate_limiter.check(ip, now + Duration::from_millis(500)));
+    assert!(!rate_limiter.check(ip, now + Duration::from_millis(500)));
+}
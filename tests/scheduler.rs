@@ -0,0 +1,132 @@
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use distributed_key_generation::{
+    task::scheduler::{
+        ExternalTriggerScheduler, FixedIntervalScheduler, RoundScheduler, WallClockScheduler,
+    },
+    types::{KeyId, KeySlotClock},
+};
+use tokio::{sync::Notify, time::Instant};
+
+const CYCLE: Duration = Duration::from_secs(10);
+
+#[test]
+fn wall_clock_boundaries_are_counted_from_the_origin() {
+    let scheduler = WallClockScheduler::new(CYCLE, 1_000);
+
+    let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+    assert_eq!(
+        scheduler.until_next_boundary(at(1_003)),
+        Duration::from_secs(7)
+    );
+    assert_eq!(
+        scheduler.until_next_boundary(at(1_019)),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn wall_clock_boundary_moment_waits_a_full_cycle() {
+    let scheduler = WallClockScheduler::new(CYCLE, 1_000);
+
+    assert_eq!(
+        scheduler.until_next_boundary(UNIX_EPOCH + Duration::from_secs(1_020)),
+        CYCLE
+    );
+}
+
+#[test]
+fn wall_clock_before_the_origin_waits_for_the_origin() {
+    let scheduler = WallClockScheduler::new(CYCLE, 1_000);
+
+    assert_eq!(
+        scheduler.until_next_boundary(UNIX_EPOCH + Duration::from_secs(955)),
+        Duration::from_secs(45)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn fixed_interval_rounds_are_a_cycle_apart() {
+    let started_at = Instant::now();
+    let mut scheduler = FixedIntervalScheduler::new(CYCLE);
+
+    let first_round = scheduler.next_round().await;
+    assert_eq!(first_round.started_at, started_at + CYCLE);
+
+    let second_round = scheduler.next_round().await;
+    assert_eq!(second_round.started_at, started_at + CYCLE * 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn fixed_interval_does_not_drift_after_a_slow_round() {
+    let started_at = Instant::now();
+    let mut scheduler = FixedIntervalScheduler::new(CYCLE);
+
+    scheduler.next_round().await;
+    tokio::time::sleep(Duration::from_secs(4)).await;
+
+    let round = scheduler.next_round().await;
+    assert_eq!(round.started_at, started_at + CYCLE * 2);
+    assert_eq!(Instant::now(), started_at + CYCLE * 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn external_trigger_is_kept_until_awaited() {
+    let trigger = Arc::new(Notify::new());
+    let mut scheduler = ExternalTriggerScheduler::new(trigger.clone());
+
+    trigger.notify_one();
+    let triggered_at = Instant::now();
+
+    let round = tokio::time::timeout(Duration::from_secs(1), scheduler.next_round())
+        .await
+        .unwrap();
+    assert_eq!(round.started_at, triggered_at);
+}
+
+#[tokio::test(start_paused = true)]
+async fn key_slot_rounds_carry_the_slot_they_were_scheduled_for() {
+    // Slots long enough that the real clock does not cross a boundary while
+    // the test runs.
+    let key_slot_clock = KeySlotClock::new(0, 1_000_000);
+    let mut scheduler = WallClockScheduler::for_key_slots(key_slot_clock);
+
+    let current_key_id = key_slot_clock.current_key_id().unwrap();
+    let round = scheduler.next_round().await;
+
+    assert_eq!(round.key_id, Some(KeyId::new(current_key_id.as_u64() + 1)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn other_rounds_carry_no_key_id() {
+    let mut scheduler = FixedIntervalScheduler::new(CYCLE);
+
+    assert_eq!(scheduler.next_round().await.key_id, None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn wall_clock_behind_the_last_boundary_does_not_repeat_it() {
+    // The paused clock jumps to the boundary while the wall clock stays
+    // before it, as when the wall clock lags the monotonic clock. The cycle
+    // is long enough that the wall clock does not reach the boundary itself.
+    let cycle = Duration::from_secs(1_000_000);
+    let mut scheduler = WallClockScheduler::new(cycle, 0);
+
+    let first_round = scheduler.next_round().await;
+    let second_round = scheduler.next_round().await;
+
+    assert!(second_round.started_at - first_round.started_at >= cycle);
+}
+
+#[tokio::test(start_paused = true)]
+async fn key_slot_rounds_never_repeat_a_key_id() {
+    let mut scheduler = WallClockScheduler::for_key_slots(KeySlotClock::new(0, 1_000_000));
+
+    let first_key_id = scheduler.next_round().await.key_id.unwrap();
+    let second_key_id = scheduler.next_round().await.key_id.unwrap();
+
+    assert_eq!(second_key_id.as_u64(), first_key_id.as_u64() + 1);
+}
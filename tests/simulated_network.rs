@@ -0,0 +1,201 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use common::{wait_for, TestCluster};
+use distributed_key_generation::{
+    rpc::transport::{
+        ClusterTransport, FaultConfig, SimulatedHandler, SimulatedNetwork, TransportError,
+    },
+    types::PartialKeyAddressList,
+};
+use serde_json::json;
+
+const LEADER: &str = "http://leader";
+const FOLLOWER: &str = "http://follower";
+
+/// Answers with `null` and records the `id` of every received parameter.
+fn recording_handler() -> (SimulatedHandler, Arc<Mutex<Vec<u64>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let log = received.clone();
+    let handler: SimulatedHandler = Arc::new(move |_method, parameter| {
+        log.lock().unwrap().push(parameter["id"].as_u64().unwrap());
+        Box::pin(async { Ok(serde_json::Value::Null) })
+    });
+
+    (handler, received)
+}
+
+/// Sends ids `0..count` concurrently and returns the order they arrived in.
+async fn arrival_order(seed: u64, count: u64) -> Vec<u64> {
+    let network = SimulatedNetwork::new(
+        seed,
+        FaultConfig {
+            max_delay: Duration::from_millis(500),
+            ..Default::default()
+        },
+    );
+    let (handler, received) = recording_handler();
+    network.register(FOLLOWER, handler);
+
+    let transport = Arc::new(network.transport(LEADER));
+    let mut sends = tokio::task::JoinSet::new();
+    for id in 0..count {
+        let transport = transport.clone();
+        sends.spawn(async move {
+            transport
+                .send(FOLLOWER, "sync_partial_key", json!({ "id": id }))
+                .await
+        });
+    }
+    while let Some(result) = sends.join_next().await {
+        result.unwrap().unwrap();
+    }
+
+    let received = received.lock().unwrap().clone();
+    received
+}
+
+#[tokio::test(start_paused = true)]
+async fn same_seed_reorders_messages_identically() {
+    let first_run = arrival_order(7, 16).await;
+
+    assert_eq!(first_run, arrival_order(7, 16).await);
+    assert_ne!(first_run, (0..16).collect::<Vec<_>>());
+}
+
+#[tokio::test(start_paused = true)]
+async fn dropped_message_times_out() {
+    let network = SimulatedNetwork::new(
+        0,
+        FaultConfig {
+            drop_probability: 1.0,
+            ..Default::default()
+        },
+    );
+    let (handler, received) = recording_handler();
+    network.register(FOLLOWER, handler);
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        network
+            .transport(LEADER)
+            .send(FOLLOWER, "sync_partial_key", json!({ "id": 0 })),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn duplicated_message_is_delivered_twice() {
+    let network = SimulatedNetwork::new(
+        0,
+        FaultConfig {
+            duplicate_probability: 1.0,
+            max_delay: Duration::from_millis(100),
+            ..Default::default()
+        },
+    );
+    let (handler, received) = recording_handler();
+    network.register(FOLLOWER, handler);
+
+    network
+        .transport(LEADER)
+        .send(FOLLOWER, "sync_partial_key", json!({ "id": 3 }))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(*received.lock().unwrap(), vec![3, 3]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn partition_blocks_both_directions_until_healed() {
+    let network = SimulatedNetwork::new(0, FaultConfig::default());
+    let (leader_handler, _) = recording_handler();
+    let (follower_handler, _) = recording_handler();
+    network.register(LEADER, leader_handler);
+    network.register(FOLLOWER, follower_handler);
+
+    network.partition(&[LEADER], &[FOLLOWER]);
+
+    let to_follower = network
+        .transport(LEADER)
+        .send(FOLLOWER, "heartbeat", json!({ "id": 0 }))
+        .await;
+    let to_leader = network
+        .transport(FOLLOWER)
+        .send(LEADER, "heartbeat", json!({ "id": 0 }))
+        .await;
+    assert!(matches!(to_follower, Err(TransportError::Unreachable)));
+    assert!(matches!(to_leader, Err(TransportError::Unreachable)));
+
+    network.heal();
+    network
+        .transport(LEADER)
+        .send(FOLLOWER, "heartbeat", json!({ "id": 0 }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn rounds_resume_when_dropped_leader_returns() {
+    let cluster = TestCluster::start(3).await;
+    cluster.run_round().await;
+
+    cluster.drop_node(0);
+    let lost_key_id = cluster.trigger_round().await;
+    let leader = &cluster.leader().context;
+    wait_for("the round without a leader to fail", || async {
+        leader.round_stats().failed == 1
+    })
+    .await;
+    for node in cluster.nodes.iter() {
+        assert_eq!(node.aggregated_key(lost_key_id).await, None);
+    }
+
+    cluster.restore_node(0);
+    cluster.wait_until_connected().await;
+    let key_id = cluster.run_round().await;
+
+    let keys = cluster.leader().keys(key_id).await;
+    for node in cluster.nodes.iter().skip(1) {
+        assert_eq!(node.keys(key_id).await, keys);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn partitioned_node_rejoins_rounds_once_healed() {
+    let cluster = TestCluster::start(3).await;
+    let leader = cluster.leader();
+    let isolated = &cluster.nodes[2];
+    let majority = [
+        cluster.nodes[0].cluster_rpc_url.as_str(),
+        cluster.nodes[1].cluster_rpc_url.as_str(),
+    ];
+
+    cluster
+        .network
+        .partition(&majority, &[isolated.cluster_rpc_url.as_str()]);
+    let key_id = cluster.run_round().await;
+
+    let keys = leader.keys(key_id).await;
+    assert_eq!(cluster.nodes[1].keys(key_id).await, keys);
+    assert_eq!(isolated.aggregated_key(key_id).await, None);
+    let participants = leader
+        .scope(async { PartialKeyAddressList::get(key_id).unwrap().to_vec() })
+        .await;
+    assert!(!participants.contains(&isolated.address));
+
+    cluster.network.heal();
+    cluster.wait_until_connected().await;
+    let key_id = cluster.run_round().await;
+
+    assert_eq!(isolated.keys(key_id).await, leader.keys(key_id).await);
+}
@@ -0,0 +1,381 @@
+mod common;
+
+use std::{path::PathBuf, str::FromStr, sync::LazyLock};
+
+use common::mock_http;
+use distributed_key_generation::{
+    client::{ClientError, ClientOptions, ClusterClient, ExternalClient},
+    database,
+    rpc::{
+        run_rpc_gateway,
+        transport::{ClusterTransport, TlsTransport, TransportError},
+        GatewayTls,
+    },
+    tls::{address_server_name, bound_address, mutual_tls_acceptor, tls_acceptor},
+    types::{ExternalTlsFiles, KeyGenerator, KeyGeneratorList, KeyId, RpcLimits, TlsFiles},
+};
+use radius_sdk::signature::{Address, ChainType};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+
+const UPSTREAM_RESPONSE: &str = r#"{"jsonrpc":"2.0","result":"ok","id":null}"#;
+
+/// Addresses the fixture certificates in `tests/fixtures/tls` are bound to.
+const NODE_ADDRESSES: [&str; 3] = [
+    "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+];
+
+/// `KeyGeneratorList` lives in the process-wide `KvStore`, so the tests that
+/// register key generators take turns.
+static DATABASE: LazyLock<Mutex<TempDir>> = LazyLock::new(|| {
+    let database_directory = TempDir::new().unwrap();
+    database::open(database_directory.path()).unwrap().init();
+
+    Mutex::new(database_directory)
+});
+
+fn tls_files(node: usize) -> TlsFiles {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+
+    TlsFiles {
+        cert_path: fixtures.join(format!("node{}.pem", node + 1)),
+        key_path: fixtures.join(format!("node{}.key", node + 1)),
+        ca_path: fixtures.join("ca.pem"),
+    }
+}
+
+/// A certificate for `localhost` and `127.0.0.1` issued by a CA of its own,
+/// and that CA.
+fn external_tls_files() -> (ExternalTlsFiles, PathBuf) {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls/external");
+
+    (
+        ExternalTlsFiles {
+            cert_path: fixtures.join("localhost.pem"),
+            key_path: fixtures.join("localhost.key"),
+        },
+        fixtures.join("ca.pem"),
+    )
+}
+
+fn address(node: usize) -> Address {
+    Address::from_str(ChainType::Ethereum, NODE_ADDRESSES[node]).unwrap()
+}
+
+fn certificate(node: usize) -> rustls::pki_types::CertificateDer<'static> {
+    let pem = std::fs::read(tls_files(node).cert_path).unwrap();
+
+    rustls_pemfile::certs(&mut pem.as_slice())
+        .next()
+        .unwrap()
+        .unwrap()
+}
+
+fn register(key_generators: &[(usize, &str)]) {
+    let mut key_generator_list = KeyGeneratorList::default();
+    for (node, cluster_rpc_url) in key_generators {
+        key_generator_list.insert(KeyGenerator::new(
+            address(*node),
+            cluster_rpc_url.to_string(),
+            "http://127.0.0.1:1".to_owned(),
+        ));
+    }
+
+    key_generator_list.put().unwrap();
+}
+
+/// Stand-in for the cluster RPC server behind the gateway.
+async fn mock_upstream() -> String {
+    mock_http(|_| ("200 OK", UPSTREAM_RESPONSE.to_owned())).await
+}
+
+fn limits() -> RpcLimits {
+    RpcLimits {
+        rate_limit_per_second: None,
+        rate_limit_burst: 0,
+        max_body_size: 1024 * 1024,
+        max_concurrent_requests: 16,
+        max_connections_per_ip: 16,
+        api_keys: Vec::new(),
+    }
+}
+
+/// Cluster gateway of `node`, requiring peers bound to a registered address.
+async fn cluster_gateway(node: usize) -> String {
+    cluster_gateway_to(node, mock_upstream().await).await
+}
+
+async fn cluster_gateway_to(node: usize, upstream_url: String) -> String {
+    let bound_address = run_rpc_gateway(
+        "127.0.0.1:0".to_owned(),
+        upstream_url,
+        limits(),
+        Some(GatewayTls {
+            acceptor: mutual_tls_acceptor(&tls_files(node)).unwrap(),
+            authorize_peers: true,
+        }),
+    )
+    .await
+    .unwrap();
+
+    format!("https://{}", bound_address)
+}
+
+#[test]
+fn address_server_name_is_the_lowercase_address() {
+    assert_eq!(
+        address_server_name(&address(0)),
+        "f39fd6e51aad88f6f4ce6ab8827279cfffb92266.key-generator.radius"
+    );
+}
+
+#[test]
+fn certificate_is_bound_to_its_address_only() {
+    let addresses: Vec<Address> = (0..3).map(address).collect();
+
+    assert_eq!(
+        bound_address(&certificate(1), addresses.iter()),
+        Some(&addresses[1])
+    );
+    assert_eq!(
+        bound_address(&certificate(1), [&addresses[0], &addresses[2]]),
+        None
+    );
+}
+
+#[tokio::test]
+async fn registered_peer_is_served() {
+    let _database = DATABASE.lock().await;
+    let url = cluster_gateway(1).await;
+    register(&[(0, "https://127.0.0.1:1"), (1, url.as_str())]);
+
+    let transport = TlsTransport::new(&tls_files(0)).unwrap();
+    let response = transport
+        .send(&url, "heartbeat", json!({ "address": NODE_ADDRESSES[0] }))
+        .await
+        .unwrap();
+
+    assert_eq!(response, Value::from("ok"));
+}
+
+#[tokio::test]
+async fn every_certificate_of_a_ca_bundle_is_trusted() {
+    let _database = DATABASE.lock().await;
+    let url = cluster_gateway(1).await;
+    register(&[(0, "https://127.0.0.1:1"), (1, url.as_str())]);
+
+    // The cluster CA comes after another certificate, as after a rotation.
+    let bundle_directory = TempDir::new().unwrap();
+    let mut ca_bundle = std::fs::read(tls_files(2).cert_path).unwrap();
+    ca_bundle.extend(std::fs::read(tls_files(0).ca_path).unwrap());
+    let ca_path = bundle_directory.path().join("ca-bundle.pem");
+    std::fs::write(&ca_path, ca_bundle).unwrap();
+
+    let transport = TlsTransport::new(&TlsFiles {
+        ca_path,
+        ..tls_files(0)
+    })
+    .unwrap();
+    let response = transport
+        .send(&url, "heartbeat", json!({ "address": NODE_ADDRESSES[0] }))
+        .await
+        .unwrap();
+
+    assert_eq!(response, Value::from("ok"));
+}
+
+#[tokio::test]
+async fn unregistered_peer_may_only_fetch_the_key_generator_list() {
+    let _database = DATABASE.lock().await;
+    let url = cluster_gateway(1).await;
+    register(&[(1, url.as_str())]);
+
+    let transport = TlsTransport::new(&tls_files(2)).unwrap();
+
+    let response = transport
+        .send(&url, "get_key_generator_list", json!({}))
+        .await
+        .unwrap();
+    assert_eq!(response, Value::from("ok"));
+
+    let result = transport
+        .send(&url, "heartbeat", json!({ "address": NODE_ADDRESSES[2] }))
+        .await;
+    assert!(matches!(result, Err(TransportError::Rejected(_))));
+}
+
+#[tokio::test]
+async fn server_bound_to_another_address_is_refused() {
+    let _database = DATABASE.lock().await;
+    // Node 2 serves the url registered for node 1.
+    let url = cluster_gateway(2).await;
+    register(&[(0, "https://127.0.0.1:1"), (1, url.as_str())]);
+
+    let transport = TlsTransport::new(&tls_files(0)).unwrap();
+    let result = transport
+        .send(&url, "heartbeat", json!({ "address": NODE_ADDRESSES[0] }))
+        .await;
+
+    assert!(matches!(result, Err(TransportError::Http(_))));
+}
+
+#[tokio::test]
+async fn client_without_certificate_is_refused() {
+    let _database = DATABASE.lock().await;
+    let url = cluster_gateway(1).await;
+    register(&[(1, url.as_str())]);
+
+    let ca_certificate =
+        reqwest::Certificate::from_pem(&std::fs::read(tls_files(1).ca_path).unwrap()).unwrap();
+    let http_client = reqwest::Client::builder()
+        .use_rustls_tls()
+        .tls_built_in_root_certs(false)
+        .add_root_certificate(ca_certificate)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .unwrap();
+
+    let result = http_client
+        .post(&url)
+        .json(
+            &json!({ "jsonrpc": "2.0", "method": "get_key_generator_list", "params": {}, "id": 1 }),
+        )
+        .send()
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn external_port_serves_its_own_certificate_without_client_authentication() {
+    // Any certificate of the external TLS settings, here one the fixture CA
+    // issued.
+    let bound_address = run_rpc_gateway(
+        "127.0.0.1:0".to_owned(),
+        mock_upstream().await,
+        limits(),
+        Some(GatewayTls {
+            acceptor: tls_acceptor(&ExternalTlsFiles {
+                cert_path: tls_files(2).cert_path,
+                key_path: tls_files(2).key_path,
+            })
+            .unwrap(),
+            authorize_peers: false,
+        }),
+    )
+    .await
+    .unwrap();
+
+    let ca_certificate =
+        reqwest::Certificate::from_pem(&std::fs::read(tls_files(2).ca_path).unwrap()).unwrap();
+    let http_client = reqwest::Client::builder()
+        .use_rustls_tls()
+        .tls_built_in_root_certs(false)
+        .add_root_certificate(ca_certificate)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .unwrap();
+
+    let response = http_client
+        .post(format!("https://{}", bound_address))
+        .json(&json!({ "jsonrpc": "2.0", "method": "get_encryption_key", "params": {}, "id": 1 }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text().await.unwrap(), UPSTREAM_RESPONSE);
+}
+
+#[tokio::test]
+async fn cluster_client_reaches_the_cluster_gateway_over_mutual_tls() {
+    let _database = DATABASE.lock().await;
+    let upstream = mock_http(|_| {
+        (
+            "200 OK",
+            json!({ "jsonrpc": "2.0", "result": { "key_generator_rpc_url_list": [] }, "id": null })
+                .to_string(),
+        )
+    })
+    .await;
+    let url = cluster_gateway_to(1, upstream).await;
+    register(&[(1, url.as_str())]);
+
+    let options = ClientOptions {
+        max_retries: 0,
+        cluster_tls: Some(tls_files(2)),
+        ..ClientOptions::default()
+    };
+    let response = ClusterClient::new(url.clone(), options)
+        .unwrap()
+        .get_key_generator_list()
+        .await
+        .unwrap();
+    assert!(response.key_generator_rpc_url_list.is_empty());
+
+    let options = ClientOptions {
+        max_retries: 0,
+        ..ClientOptions::default()
+    };
+    let result = ClusterClient::new(url, options)
+        .unwrap()
+        .get_key_generator_list()
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn external_client_trusts_its_ca_bundle_and_sends_its_api_key() {
+    let upstream = mock_http(|_| {
+        (
+            "200 OK",
+            json!({ "jsonrpc": "2.0", "result": { "latest_key_id": 7 }, "id": null }).to_string(),
+        )
+    })
+    .await;
+    let (external_tls, ca_path) = external_tls_files();
+    let bound_address = run_rpc_gateway(
+        "127.0.0.1:0".to_owned(),
+        upstream,
+        RpcLimits {
+            api_keys: vec!["secret".to_owned()],
+            ..limits()
+        },
+        Some(GatewayTls {
+            acceptor: tls_acceptor(&external_tls).unwrap(),
+            authorize_peers: false,
+        }),
+    )
+    .await
+    .unwrap();
+    let url = format!("https://{}", bound_address);
+
+    let options = ClientOptions {
+        max_retries: 0,
+        api_key: Some("secret".to_owned()),
+        ca_path: Some(ca_path.clone()),
+        ..ClientOptions::default()
+    };
+    let response = ExternalClient::new(vec![url.clone()], options)
+        .unwrap()
+        .get_latest_key_id()
+        .await
+        .unwrap();
+    assert_eq!(response.latest_key_id, KeyId::new(7));
+
+    let options = ClientOptions {
+        max_retries: 0,
+        ca_path: Some(ca_path),
+        ..ClientOptions::default()
+    };
+    let result = ExternalClient::new(vec![url], options)
+        .unwrap()
+        .get_latest_key_id()
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::Transport(TransportError::Rejected(_)))
+    ));
+}